@@ -1,25 +1,34 @@
+use shared::error::SolveError;
 use shared::parse::Parsable;
 use shared::*;
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(1, "Secret Entrance", [part_1, part_2], parse => [solve_1, solve_2]);
 
-fn parse(input: &str) -> Vec<i32> {
+pub mod reference;
+
+fn parse(input: &str) -> Result<Vec<i32>, SolveError> {
     let mut instructions = Vec::new();
 
-    for line in input.lines() {
+    for (y, line) in input.lines().enumerate() {
         let mut bytes = line.bytes();
 
-        let positive = bytes.next().unwrap() == b'R';
-        let number: i32 = bytes.next_number().unwrap();
+        let positive = match bytes.next() {
+            Some(b'R') => true,
+            Some(b'L') => false,
+            _ => return Err(SolveError::at(y + 1, 1, "expected 'L' or 'R'")),
+        };
+        let number: i32 = bytes
+            .next_number()
+            .ok_or_else(|| SolveError::at(y + 1, 2, "expected number"))?;
         instructions.push(if positive { number } else { -number });
     }
 
-    instructions
+    Ok(instructions)
 }
 
-fn solve_1(instructions: &Vec<i32>) -> usize {
+fn solve_1(instructions: &Vec<i32>) -> Result<usize, SolveError> {
     let mut i = 50;
     let mut solution = 0;
 
@@ -31,31 +40,41 @@ fn solve_1(instructions: &Vec<i32>) -> usize {
         }
     }
 
-    return solution;
+    Ok(solution)
 }
 
-pub fn part_1(_input: &str) -> Solution {
-    let instructions = parse(_input);
-    solve_1(&instructions).into()
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    let instructions = parse(_input)?;
+    Ok(solve_1(&instructions)?.into())
 }
 
 #[cfg(test)]
 mod part_1_tests {
     use crate::*;
+    use shared::error::Position;
     use test_case::test_case;
 
     #[test_case(include_str!("_test.txt"), 3)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
+    }
+
+    #[test_case("R10\nX5", 2, 1)]
+    #[test_case("R10\nL", 2, 2)]
+    fn invalid_input(input: &str, line: usize, column: usize) {
+        let error = part_1(input).unwrap_err();
+        assert_eq!(error.position, Some(Position::new(line, column)));
     }
 
     #[test_case(1023)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
-fn solve_2(instructions: &Vec<i32>) -> i32 {
+fn solve_2(instructions: &Vec<i32>) -> Result<i32, SolveError> {
     let mut i = 50;
     let mut solution = 0;
 
@@ -73,12 +92,12 @@ fn solve_2(instructions: &Vec<i32>) -> i32 {
         i = i.rem_euclid(100);
     }
 
-    return solution;
+    Ok(solution)
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    let instructions = parse(_input);
-    solve_2(&instructions).into()
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    let instructions = parse(_input)?;
+    Ok(solve_2(&instructions)?.into())
 }
 
 #[cfg(test)]
@@ -89,11 +108,26 @@ mod part_2_tests {
     #[test_case(include_str!("_test.txt"), 6)]
     #[test_case(include_str!("_manual.txt"), 11)]
     fn example_input(input: &str, expected: i32) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(5899)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: i32) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{day::Part, differential::assert_matches, generate};
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1)]
+    #[test_case(part_2, reference::part_2)]
+    fn matches_reference(fast: Part, reference: Part) {
+        assert_matches(fast, reference, generate::rotations, 50, 0..100);
     }
 }
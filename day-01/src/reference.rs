@@ -0,0 +1,50 @@
+use shared::{Solution, error::SolveError};
+
+/// The positions of the dial after every click, one list per rotation.
+fn clicks(input: &str) -> Result<Vec<Vec<i32>>, SolveError> {
+    let mut dial = 50;
+    let mut rotations = Vec::new();
+
+    for (y, line) in input.lines().enumerate() {
+        let step = match line.as_bytes().first() {
+            Some(b'R') => 1,
+            Some(b'L') => -1,
+            _ => return Err(SolveError::at(y + 1, 1, "expected 'L' or 'R'")),
+        };
+        let distance: u32 = line[1..]
+            .parse()
+            .map_err(|_| SolveError::at(y + 1, 2, "expected number"))?;
+
+        let mut positions = Vec::new();
+        for _ in 0..distance {
+            dial = (dial + step + 100) % 100;
+            positions.push(dial);
+        }
+        rotations.push(positions);
+    }
+
+    Ok(rotations)
+}
+
+/// Turns the dial one click at a time, looking at it after every rotation.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    let mut dial = 50;
+    let mut zeros = 0;
+    for positions in clicks(input)? {
+        dial = positions.last().copied().unwrap_or(dial);
+        if dial == 0 {
+            zeros += 1;
+        }
+    }
+    Ok(zeros.into())
+}
+
+/// Turns the dial one click at a time, looking at it after every click.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    let zeros = clicks(input)?
+        .concat()
+        .iter()
+        .filter(|&&dial| dial == 0)
+        .count();
+    Ok(zeros.into())
+}
@@ -1,19 +1,40 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, str::Bytes};
 
+use shared::error::SolveError;
 use shared::math::Digits;
-use shared::parse::Parsable;
+use shared::parse::ParsableChecked;
 use shared::*;
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(2, "Gift Shop", [part_1, part_2], parse => [solve_1, solve_2]);
 
-fn solve_1(input: &str) -> usize {
+pub mod reference;
+
+fn next_number(input: &str, bytes: &mut Bytes) -> Result<Option<usize>, SolveError> {
+    bytes
+        .next_number_checked()
+        .map_err(|error| SolveError::at_offset(input, input.len() - bytes.len(), error.to_string()))
+}
+
+fn parse(input: &str) -> Result<Vec<(usize, usize)>, SolveError> {
     let mut bytes = input.bytes();
+    let mut ranges = Vec::new();
+
+    while let Some(start) = next_number(input, &mut bytes)? {
+        let end = next_number(input, &mut bytes)?.ok_or_else(|| {
+            SolveError::at_offset(input, input.len() - bytes.len(), "expected end of range")
+        })?;
+        ranges.push((start, end));
+    }
+
+    Ok(ranges)
+}
+
+fn solve_1(ranges: &[(usize, usize)]) -> Result<usize, SolveError> {
     let mut answer: usize = 0;
 
-    while let Some(start) = bytes.next_number() as Option<usize> {
-        let end: usize = bytes.next_number().unwrap();
+    for &(start, end) in ranges {
         let start_digits = start.count_digits();
         let end_digits = end.count_digits();
 
@@ -38,11 +59,11 @@ fn solve_1(input: &str) -> usize {
         }
     }
 
-    answer
+    Ok(answer)
 }
 
-pub fn part_1(_input: &str) -> Solution {
-    solve_1(_input).into()
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse(_input)?)?.into())
 }
 
 #[cfg(test)]
@@ -54,23 +75,23 @@ mod part_1_tests {
     #[test_case(include_str!("_manual_1.txt"), 495900)]
     #[test_case(include_str!("_manual_2.txt"), 495495949990950)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
     }
 
     #[test_case(19219508902)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
-fn solve_2(input: &str) -> usize {
-    let mut bytes = input.bytes();
+fn solve_2(ranges: &[(usize, usize)]) -> Result<usize, SolveError> {
     let mut answer: usize = 0;
 
     let mut map: HashSet<usize> = HashSet::new();
 
-    while let Some(start) = bytes.next_number() as Option<usize> {
-        let end: usize = bytes.next_number().unwrap();
+    for &(start, end) in ranges {
         let start_digits = start.count_digits();
         let end_digits = end.count_digits();
 
@@ -104,11 +125,11 @@ fn solve_2(input: &str) -> usize {
         }
     }
 
-    answer
+    Ok(answer)
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    solve_2(_input).into()
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_2(&parse(_input)?)?.into())
 }
 
 #[cfg(test)]
@@ -119,11 +140,26 @@ mod part_2_tests {
     #[test_case(include_str!("_test.txt"), 4174379265)]
     #[test_case(include_str!("_manual_3.txt"), 512006)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(27180728081)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{day::Part, differential::assert_matches, generate};
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1)]
+    #[test_case(part_2, reference::part_2)]
+    fn matches_reference(fast: Part, reference: Part) {
+        assert_matches(fast, reference, generate::ranges, 10, 0..100);
     }
 }
@@ -0,0 +1,39 @@
+use shared::{Solution, error::SolveError};
+
+fn ids(input: &str) -> Result<impl Iterator<Item = u64>, SolveError> {
+    let ranges = input
+        .trim()
+        .split(',')
+        .map(|range| {
+            let (start, end) = range
+                .split_once('-')
+                .ok_or_else(|| SolveError::new(format!("expected a range, found '{}'", range)))?;
+            let number = |n: &str| {
+                n.parse::<u64>()
+                    .map_err(|_| SolveError::new(format!("expected a number, found '{}'", n)))
+            };
+            Ok(number(start)?..=number(end)?)
+        })
+        .collect::<Result<Vec<_>, SolveError>>()?;
+
+    Ok(ranges.into_iter().flatten())
+}
+
+fn repeats(id: u64, times: usize) -> bool {
+    let id = id.to_string();
+    id.len().is_multiple_of(times) && id == id[..id.len() / times].repeat(times)
+}
+
+/// Checks every ID in every range for a sequence repeated twice.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    let sum: u64 = ids(input)?.filter(|&id| repeats(id, 2)).sum();
+    Ok(sum.into())
+}
+
+/// Checks every ID in every range for a sequence repeated any number of times.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    let sum: u64 = ids(input)?
+        .filter(|&id| (2..=id.to_string().len()).any(|times| repeats(id, times)))
+        .sum();
+    Ok(sum.into())
+}
@@ -1,15 +1,17 @@
-use shared::*;
+use shared::{error::SolveError, *};
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(3, "Lobby", [part_1, part_2], parse => [solve_1, solve_2]);
 
-pub fn part_1(_input: &str) -> Solution {
-    _input
-        .lines()
-        .filter_map(|line| solve(line.as_bytes(), 0, 2))
-        .sum::<usize>()
-        .into()
+pub mod reference;
+
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse(_input)?)?.into())
+}
+
+fn solve_1(banks: &[Vec<u8>]) -> Result<usize, SolveError> {
+    joltage(banks, 2)
 }
 
 #[cfg(test)]
@@ -19,21 +21,54 @@ mod part_1_tests {
 
     #[test_case(include_str!("_test.txt"), 357)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
     }
 
     #[test_case(17166)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    _input
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_2(&parse(_input)?)?.into())
+}
+
+fn solve_2(banks: &[Vec<u8>]) -> Result<usize, SolveError> {
+    joltage(banks, 12)
+}
+
+fn parse(input: &str) -> Result<Vec<Vec<u8>>, SolveError> {
+    input
         .lines()
-        .filter_map(|line| solve(line.as_bytes(), 0, 12))
-        .sum::<usize>()
-        .into()
+        .enumerate()
+        .map(|(y, line)| {
+            if let Some(x) = line.bytes().position(|b| !b.is_ascii_digit()) {
+                return Err(SolveError::at(y + 1, x + 1, "expected digit"));
+            }
+            Ok(line.as_bytes().to_vec())
+        })
+        .collect()
+}
+
+fn joltage(banks: &[Vec<u8>], digits: usize) -> Result<usize, SolveError> {
+    let mut total = 0;
+
+    for (y, bank) in banks.iter().enumerate() {
+        if bank.len() < digits {
+            return Err(SolveError::at(
+                y + 1,
+                bank.len() + 1,
+                format!("expected at least {} digits", digits),
+            ));
+        }
+
+        total += solve(bank, 0, digits).unwrap_or(0);
+    }
+
+    Ok(total)
 }
 
 fn solve(bytes: &[u8], pos: usize, rem: usize) -> Option<usize> {
@@ -66,11 +101,26 @@ mod part_2_tests {
 
     #[test_case(include_str!("_test.txt"), 3121910778619)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(169077317650774)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{day::Part, differential::assert_matches, generate};
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1)]
+    #[test_case(part_2, reference::part_2)]
+    fn matches_reference(fast: Part, reference: Part) {
+        assert_matches(fast, reference, generate::banks, 20, 0..100);
     }
 }
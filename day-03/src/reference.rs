@@ -0,0 +1,53 @@
+use shared::{Solution, error::SolveError};
+
+fn banks(input: &str) -> Result<Vec<Vec<usize>>, SolveError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    c.to_digit(10)
+                        .map(|digit| digit as usize)
+                        .ok_or_else(|| SolveError::at(y + 1, x + 1, "expected digit"))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The largest joltage of any `count` batteries, taken in order, trying every choice.
+fn largest(bank: &[usize], count: usize, joltage: usize) -> Option<usize> {
+    if count == 0 {
+        return Some(joltage);
+    }
+
+    (0..bank.len())
+        .filter_map(|i| largest(&bank[i + 1..], count - 1, joltage * 10 + bank[i]))
+        .max()
+}
+
+fn total(input: &str, count: usize) -> Result<Solution, SolveError> {
+    let mut total = 0;
+    for (y, bank) in banks(input)?.iter().enumerate() {
+        total += largest(bank, count, 0).ok_or_else(|| {
+            SolveError::at(
+                y + 1,
+                bank.len() + 1,
+                format!("expected at least {} digits", count),
+            )
+        })?;
+    }
+    Ok(total.into())
+}
+
+/// Tries every pair of batteries in each bank.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    total(input, 2)
+}
+
+/// Tries every twelve batteries in each bank.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    total(input, 12)
+}
@@ -1,29 +1,24 @@
-use shared::{grid::Grid, points::point::Point, *};
+use shared::{error::SolveError, grid::Grid, points::point::Point, *};
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(4, "Printing Department", [part_1, part_2], parse => [solve_1, solve_2]);
 
-pub fn part_1(_input: &str) -> Solution {
-    let grid = parse(_input);
-    grid.points()
-        .filter(|&point| grid[point] && grid.adjacent_eight(point).filter(|&a| grid[a]).count() < 4)
-        .count()
-        .into()
-}
+pub mod reference;
 
-fn parse(input: &str) -> Grid<bool> {
-    let mut vec = Vec::new();
-    let mut height = 0;
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse(_input)?)?.into())
+}
 
-    for line in input.lines() {
-        for byte in line.bytes() {
-            vec.push(byte == b'@');
-        }
-        height += 1;
-    }
+fn solve_1(grid: &Grid<bool>) -> Result<usize, SolveError> {
+    Ok(grid
+        .points()
+        .filter(|&point| grid[point] && grid.adjacent_eight(point).filter(|&a| grid[a]).count() < 4)
+        .count())
+}
 
-    Grid::from(vec, height)
+fn parse(input: &str) -> Result<Grid<bool>, SolveError> {
+    Grid::parse(input, |byte| byte == b'@')
 }
 
 #[cfg(test)]
@@ -33,24 +28,30 @@ mod part_1_tests {
 
     #[test_case(include_str!("_test.txt"), 13)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
     }
 
     #[test_case(1604)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    let mut grid = parse(_input);
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_2(&parse(_input)?)?.into())
+}
+
+fn solve_2(grid: &Grid<bool>) -> Result<usize, SolveError> {
+    let mut grid = grid.clone();
     let mut count: usize = 0;
 
     for point in grid.points() {
         fill(&point, &mut grid, &mut count);
     }
 
-    count.into()
+    Ok(count)
 }
 
 fn fill(point: &Point, grid: &mut Grid<bool>, count: &mut usize) {
@@ -71,11 +72,26 @@ mod part_2_tests {
 
     #[test_case(include_str!("_test.txt"), 43)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(9397)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{day::Part, differential::assert_matches, generate};
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1)]
+    #[test_case(part_2, reference::part_2)]
+    fn matches_reference(fast: Part, reference: Part) {
+        assert_matches(fast, reference, generate::rolls, 12, 0..100);
     }
 }
@@ -0,0 +1,70 @@
+use shared::{Solution, error::SolveError};
+
+fn rolls(input: &str) -> Result<Vec<Vec<bool>>, SolveError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, c)| match c {
+                    '@' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(SolveError::at(y + 1, x + 1, "expected '@' or '.'")),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Every roll with fewer than four rolls among the eight cells around it.
+fn accessible(rolls: &[Vec<bool>]) -> Vec<(usize, usize)> {
+    let at = |x: isize, y: isize| -> bool {
+        y >= 0
+            && x >= 0
+            && rolls
+                .get(y as usize)
+                .and_then(|row| row.get(x as usize))
+                .copied()
+                .unwrap_or(false)
+    };
+
+    let mut accessible = Vec::new();
+    for (y, row) in rolls.iter().enumerate() {
+        for (x, &roll) in row.iter().enumerate() {
+            let (x, y) = (x as isize, y as isize);
+            let around = (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                .filter(|&(dx, dy)| (dx, dy) != (0, 0) && at(x + dx, y + dy))
+                .count();
+            if roll && around < 4 {
+                accessible.push((x as usize, y as usize));
+            }
+        }
+    }
+    accessible
+}
+
+/// Counts the neighbours of every roll.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    Ok(accessible(&rolls(input)?).len().into())
+}
+
+/// Removes every accessible roll at once, over and over until none is left.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    let mut rolls = rolls(input)?;
+    let mut removed = 0;
+
+    loop {
+        let accessible = accessible(&rolls);
+        if accessible.is_empty() {
+            break;
+        }
+        removed += accessible.len();
+        for (x, y) in accessible {
+            rolls[y][x] = false;
+        }
+    }
+
+    Ok(removed.into())
+}
@@ -1,40 +1,44 @@
 #![feature(iter_advance_by)]
 use std::ops::Range;
 
-use shared::{parse::Parsable, *};
+use shared::{error::SolveError, parse::cursor::Cursor, *};
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(5, "Cafeteria", [part_1, part_2], parse => [solve_1, solve_2]);
 
-fn parse(input: &str) -> (Vec<Range<usize>>, Vec<usize>) {
+pub mod reference;
+
+fn parse(input: &str) -> Result<(Vec<Range<usize>>, Vec<usize>), SolveError> {
     let mut ranges = Vec::new();
     let mut ids = Vec::new();
 
-    let mut lines = input.lines();
+    let mut cursor = Cursor::new(input);
 
-    while let Some(line) = lines.next() {
-        if line.is_empty() {
-            break;
-        }
+    while cursor.peek() != Some(b'\n') && !cursor.is_empty() {
+        let start = cursor.expect_number()?;
+        cursor.expect_byte(b'-')?;
+        let end: usize = cursor.expect_number()?;
+        ranges.push(start..end + 1);
+        cursor.expect_byte(b'\n')?;
+    }
+    cursor.skip_line();
 
-        let mut bytes = line.bytes();
-        let mut range = Range {
-            start: bytes.next_number().unwrap(),
-            end: bytes.next_number().unwrap(),
-        };
-        range.end += 1;
-        ranges.push(range);
+    while !cursor.is_empty() {
+        ids.push(cursor.expect_number()?);
+        if !cursor.is_empty() {
+            cursor.expect_byte(b'\n')?;
+        }
     }
 
-    while let Some(line) = lines.next() {
-        ids.push(line.bytes().next_number().unwrap());
+    if ranges.is_empty() {
+        return Err(SolveError::new("expected at least one range"));
     }
 
-    (ranges, ids)
+    Ok((consolidate(ranges), ids))
 }
 
-fn consolidate(ranges: &mut Vec<Range<usize>>) -> Vec<Range<usize>> {
+fn consolidate(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
     ranges.sort_by(|a, b| a.start.cmp(&b.start).then(a.end.cmp(&b.end)));
     let mut consolidated: Vec<Range<usize>> = Vec::new();
 
@@ -55,16 +59,18 @@ fn consolidate(ranges: &mut Vec<Range<usize>>) -> Vec<Range<usize>> {
         }
 
         if !added {
-            consolidated.push(next.clone());
+            consolidated.push(next);
         }
     }
 
     consolidated
 }
 
-pub fn part_1(_input: &str) -> Solution {
-    let (mut ranges, ids) = parse(_input);
-    let consolidated = consolidate(&mut ranges);
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse(_input)?)?.into())
+}
+
+fn solve_1((consolidated, ids): &(Vec<Range<usize>>, Vec<usize>)) -> Result<usize, SolveError> {
     let outer_range = Range {
         start: consolidated.first().unwrap().start,
         end: consolidated.last().unwrap().end,
@@ -72,42 +78,53 @@ pub fn part_1(_input: &str) -> Solution {
 
     let mut fresh_count: usize = 0;
     for id in ids {
-        if outer_range.contains(&id) {
+        if outer_range.contains(id) {
             for range in consolidated.iter() {
-                if range.contains(&id) {
+                if range.contains(id) {
                     fresh_count += 1;
                     break;
                 }
             }
         }
     }
-    fresh_count.into()
+    Ok(fresh_count)
 }
 
 #[cfg(test)]
 mod part_1_tests {
     use crate::*;
+    use shared::error::Position;
     use test_case::test_case;
 
     #[test_case(include_str!("_test.txt"), 3)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
+    }
+
+    #[test_case("3-5\n10\n\n1", 2, 3)]
+    #[test_case("3-5\n\nx", 3, 1)]
+    fn invalid_input(input: &str, line: usize, column: usize) {
+        let error = part_1(input).unwrap_err();
+        assert_eq!(error.position, Some(Position::new(line, column)));
     }
 
     #[test_case(601)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    let (mut ranges, _ids) = parse(_input);
-    let consolidated = consolidate(&mut ranges);
-    consolidated
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_2(&parse(_input)?)?.into())
+}
+
+fn solve_2((consolidated, _ids): &(Vec<Range<usize>>, Vec<usize>)) -> Result<usize, SolveError> {
+    Ok(consolidated
         .iter()
         .map(|range| range.end - range.start)
-        .sum::<usize>()
-        .into()
+        .sum::<usize>())
 }
 
 #[cfg(test)]
@@ -117,11 +134,26 @@ mod part_2_tests {
 
     #[test_case(include_str!("_test.txt"), 14)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(367899984917516)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{day::Part, differential::assert_matches, generate};
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1)]
+    #[test_case(part_2, reference::part_2)]
+    fn matches_reference(fast: Part, reference: Part) {
+        assert_matches(fast, reference, generate::ingredients, 20, 0..100);
     }
 }
@@ -0,0 +1,58 @@
+use shared::{Solution, error::SolveError};
+
+/// Inclusive ranges of fresh IDs, and the available IDs.
+type Database = (Vec<(usize, usize)>, Vec<usize>);
+
+fn database(input: &str) -> Result<Database, SolveError> {
+    let (ranges, ids) = input
+        .split_once("\n\n")
+        .ok_or_else(|| SolveError::new("expected a blank line"))?;
+    let number = |y: usize, text: &str| {
+        text.parse()
+            .map_err(|_| SolveError::at(y + 1, 1, "expected number"))
+    };
+
+    let ranges = ranges
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            let (start, end) = line
+                .split_once('-')
+                .ok_or_else(|| SolveError::at(y + 1, 1, "expected '-'"))?;
+            Ok((number(y, start)?, number(y, end)?))
+        })
+        .collect::<Result<Vec<_>, SolveError>>()?;
+    if ranges.is_empty() {
+        return Err(SolveError::new("expected at least one range"));
+    }
+
+    let skipped = ranges.len() + 1;
+    let ids = ids
+        .lines()
+        .enumerate()
+        .map(|(y, line)| number(skipped + y, line))
+        .collect::<Result<_, _>>()?;
+
+    Ok((ranges, ids))
+}
+
+fn fresh(ranges: &[(usize, usize)], id: usize) -> bool {
+    ranges.iter().any(|&(start, end)| start <= id && id <= end)
+}
+
+/// Checks every ID against every range.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    let (ranges, ids) = database(input)?;
+    Ok(ids.iter().filter(|&&id| fresh(&ranges, id)).count().into())
+}
+
+/// Checks every ID from the lowest start to the highest end against every range.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    let (ranges, _) = database(input)?;
+    let lowest = ranges.iter().map(|&(start, _)| start).min().unwrap_or(0);
+    let highest = ranges.iter().map(|&(_, end)| end).max().unwrap_or(0);
+    Ok((lowest..=highest)
+        .filter(|&id| fresh(&ranges, id))
+        .count()
+        .into())
+}
@@ -1,17 +1,42 @@
 use std::{iter::from_fn, usize};
 
 use shared::{
+    error::SolveError,
     parse::{Parsable, ParsableNonWhitespaceByte},
     *,
 };
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(6, "Trash Compactor", [part_1, part_2], parse => [solve_1, solve_2]);
 
-pub fn part_1(_input: &str) -> Solution {
-    let mut lines = _input.lines().map(|line| line.bytes()).collect::<Vec<_>>();
-    let mut instructions = lines.pop().unwrap();
+pub mod reference;
+
+struct Worksheet {
+    rows: Vec<Vec<u8>>,
+    operators: Vec<u8>,
+}
+
+fn parse(input: &str) -> Result<Worksheet, SolveError> {
+    let mut rows: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+    let operators = rows
+        .pop()
+        .ok_or_else(|| SolveError::new("expected a line of operators"))?;
+
+    Ok(Worksheet { rows, operators })
+}
+
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse(_input)?)?.into())
+}
+
+fn solve_1(worksheet: &Worksheet) -> Result<usize, SolveError> {
+    let mut lines = worksheet
+        .rows
+        .iter()
+        .map(|row| row.iter().copied())
+        .collect::<Vec<_>>();
+    let mut instructions = worksheet.operators.iter().copied();
     let mut solution: usize = 0;
 
     loop {
@@ -35,7 +60,7 @@ pub fn part_1(_input: &str) -> Solution {
             break;
         }
     }
-    solution.into()
+    Ok(solution)
 }
 
 #[cfg(test)]
@@ -45,18 +70,28 @@ mod part_1_tests {
 
     #[test_case(include_str!("_test.txt"), 4277556)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
     }
 
     #[test_case(5873191732773)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    let mut lines: Vec<_> = _input.lines().map(|line| line.bytes()).collect();
-    let mut instructions = lines.pop().unwrap();
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_2(&parse(_input)?)?.into())
+}
+
+fn solve_2(worksheet: &Worksheet) -> Result<usize, SolveError> {
+    let mut lines: Vec<_> = worksheet
+        .rows
+        .iter()
+        .map(|row| row.iter().copied())
+        .collect();
+    let mut instructions = worksheet.operators.iter().copied();
     let mut solution: usize = 0;
 
     loop {
@@ -71,7 +106,7 @@ pub fn part_2(_input: &str) -> Solution {
             break;
         }
     }
-    solution.into()
+    Ok(solution)
 }
 
 fn next_column_number<T: Iterator<Item = u8>>(lines: &mut Vec<T>) -> Option<usize> {
@@ -94,11 +129,26 @@ mod part_2_tests {
 
     #[test_case(include_str!("_test.txt"), 3263827)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(11386445308378)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{day::Part, differential::assert_matches, generate};
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1)]
+    #[test_case(part_2, reference::part_2)]
+    fn matches_reference(fast: Part, reference: Part) {
+        assert_matches(fast, reference, generate::worksheet, 10, 0..100);
     }
 }
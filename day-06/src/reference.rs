@@ -0,0 +1,82 @@
+use shared::{Solution, error::SolveError};
+
+struct Problem {
+    operator: u8,
+    columns: Vec<Vec<u8>>,
+}
+
+/// The columns of each problem, split wherever a column is blank on every line, along with
+/// the problem's operator.
+fn problems(input: &str) -> Result<Vec<Problem>, SolveError> {
+    let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    let column = |x: usize| -> Vec<u8> {
+        lines
+            .iter()
+            .map(|line| line.get(x).copied().unwrap_or(b' '))
+            .collect()
+    };
+
+    let mut problems = Vec::new();
+    let mut columns: Vec<Vec<u8>> = Vec::new();
+    for x in 0..=width {
+        let next = column(x);
+        if x < width && next.iter().any(|&byte| byte != b' ') {
+            columns.push(next);
+            continue;
+        }
+        if columns.is_empty() {
+            continue;
+        }
+
+        let operator = columns
+            .iter()
+            .filter_map(|column| column.last().copied())
+            .find(|&byte| byte != b' ')
+            .ok_or_else(|| SolveError::at(lines.len(), x + 1, "expected '*' or '+'"))?;
+        problems.push(Problem { operator, columns });
+        columns = Vec::new();
+    }
+
+    Ok(problems)
+}
+
+/// The number in a run of digits and spaces, read in order.
+fn number(digits: impl Iterator<Item = u8>) -> Option<usize> {
+    let digits: Vec<u8> = digits.filter(|&byte| byte != b' ').collect();
+    std::str::from_utf8(&digits).ok()?.parse().ok()
+}
+
+fn apply(operator: u8, numbers: Vec<usize>) -> Result<usize, SolveError> {
+    match operator {
+        b'*' => Ok(numbers.iter().product()),
+        b'+' => Ok(numbers.iter().sum()),
+        _ => Err(SolveError::new("expected '*' or '+'")),
+    }
+}
+
+/// Reads every problem row by row.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    let mut total = 0;
+    for Problem { operator, columns } in problems(input)? {
+        let rows = columns[0].len() - 1;
+        let numbers = (0..rows)
+            .filter_map(|y| number(columns.iter().map(|column| column[y])))
+            .collect();
+        total += apply(operator, numbers)?;
+    }
+    Ok(total.into())
+}
+
+/// Reads every problem column by column.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    let mut total = 0;
+    for Problem { operator, columns } in problems(input)? {
+        let numbers = columns
+            .iter()
+            .filter_map(|column| number(column[..column.len() - 1].iter().copied()))
+            .collect();
+        total += apply(operator, numbers)?;
+    }
+    Ok(total.into())
+}
@@ -1,20 +1,30 @@
-use shared::Solution;
+use shared::{Solution, error::SolveError};
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(7, "Laboratories", [part_1, part_2], parse => [solve_1, solve_2]);
 
-pub fn part_1(_input: &str) -> Solution {
-    let (lines, start, width) = parse(_input);
+pub mod reference;
 
-    let mut beams: Vec<bool> = vec![false; width];
+struct Manifold {
+    splitters: Vec<Vec<usize>>,
+    start: usize,
+    width: usize,
+}
+
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse(_input)?)?.into())
+}
+
+fn solve_1(manifold: &Manifold) -> Result<usize, SolveError> {
+    let mut beams: Vec<bool> = vec![false; manifold.width];
     let mut count: usize = 0;
 
-    beams[start] = true;
+    beams[manifold.start] = true;
 
-    for line in lines {
-        for (x, b) in line.bytes().enumerate() {
-            if b == b'^' && beams[x] {
+    for row in manifold.splitters.iter() {
+        for &x in row {
+            if beams[x] {
                 beams[x - 1] = true;
                 beams[x] = false;
                 beams[x + 1] = true;
@@ -23,23 +33,38 @@ pub fn part_1(_input: &str) -> Solution {
         }
     }
 
-    count.into()
+    Ok(count)
 }
 
-fn parse(input: &str) -> (impl Iterator<Item = &str>, usize, usize) {
+fn parse(input: &str) -> Result<Manifold, SolveError> {
     let mut lines = input.lines().step_by(2);
-    let mut start = 0;
+    let mut start = None;
     let mut width = 0;
     if let Some(line) = lines.next() {
         for byte in line.bytes() {
             if byte == b'S' {
-                start = width;
+                start = Some(width);
             }
             width += 1;
         }
     }
 
-    (lines, start, width)
+    let start = start.ok_or_else(|| SolveError::at(1, width + 1, "expected 'S'"))?;
+    let splitters = lines
+        .map(|line| {
+            line.bytes()
+                .enumerate()
+                .filter(|&(_, b)| b == b'^')
+                .map(|(x, _)| x)
+                .collect()
+        })
+        .collect();
+
+    Ok(Manifold {
+        splitters,
+        start,
+        width,
+    })
 }
 
 #[cfg(test)]
@@ -49,31 +74,34 @@ mod part_1_tests {
 
     #[test_case(include_str!("_test.txt"), 21)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
     }
 
     #[test_case(1587)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    let (lines, start, width) = parse(_input);
-    let mut beams: Vec<usize> = vec![0; width];
-    beams[start] = 1;
-
-    for line in lines {
-        for (x, b) in line.bytes().enumerate() {
-            if b == b'^' {
-                beams[x - 1] += beams[x];
-                beams[x + 1] += beams[x];
-                beams[x] = 0;
-            }
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_2(&parse(_input)?)?.into())
+}
+
+fn solve_2(manifold: &Manifold) -> Result<usize, SolveError> {
+    let mut beams: Vec<usize> = vec![0; manifold.width];
+    beams[manifold.start] = 1;
+
+    for row in manifold.splitters.iter() {
+        for &x in row {
+            beams[x - 1] += beams[x];
+            beams[x + 1] += beams[x];
+            beams[x] = 0;
         }
     }
 
-    beams.iter().sum::<usize>().into()
+    Ok(beams.iter().sum::<usize>())
 }
 
 #[cfg(test)]
@@ -83,11 +111,26 @@ mod part_2_tests {
 
     #[test_case(include_str!("_test.txt"), 40)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(5748679033029)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{day::Part, differential::assert_matches, generate};
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1)]
+    #[test_case(part_2, reference::part_2)]
+    fn matches_reference(fast: Part, reference: Part) {
+        assert_matches(fast, reference, generate::manifold, 10, 0..100);
     }
 }
@@ -0,0 +1,55 @@
+use shared::{Solution, error::SolveError};
+
+fn manifold(input: &str) -> Result<(Vec<&[u8]>, usize), SolveError> {
+    let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let first = lines.first().copied().unwrap_or_default();
+    let start = first
+        .iter()
+        .position(|&byte| byte == b'S')
+        .ok_or_else(|| SolveError::at(1, first.len() + 1, "expected 'S'"))?;
+    Ok((lines, start))
+}
+
+fn splits(lines: &[&[u8]], y: usize, x: usize) -> bool {
+    lines[y].get(x) == Some(&b'^')
+}
+
+/// Moves every beam down one line at a time.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    let (lines, start) = manifold(input)?;
+    let mut beams = vec![start];
+    let mut count = 0;
+
+    for y in 1..lines.len() {
+        let mut next = Vec::new();
+        for x in beams {
+            if splits(&lines, y, x) {
+                count += 1;
+                next.extend([x - 1, x + 1]);
+            } else {
+                next.push(x);
+            }
+        }
+        next.sort();
+        next.dedup();
+        beams = next;
+    }
+
+    Ok(count.into())
+}
+
+/// Follows every timeline on its own.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    fn timelines(lines: &[&[u8]], y: usize, x: usize) -> usize {
+        if y == lines.len() {
+            1
+        } else if splits(lines, y, x) {
+            timelines(lines, y + 1, x - 1) + timelines(lines, y + 1, x + 1)
+        } else {
+            timelines(lines, y + 1, x)
+        }
+    }
+
+    let (lines, start) = manifold(input)?;
+    Ok(timelines(&lines, 1, start).into())
+}
@@ -1,16 +1,29 @@
-use shared::{parse::Parsable, points::vector::Vector, *};
+use shared::{error::SolveError, parse::Parsable, points::vector::Vector, *};
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(8, "Playground", [part_1, part_2], parse => [solve_1, solve_2]);
 
-pub fn part_1(_input: &str) -> Solution {
-    solve_1(_input, 1_000).into()
+pub mod reference;
+
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse(_input)?)?.into())
+}
+
+fn solve_1(points: &Vec<Vector<u64>>) -> Result<usize, SolveError> {
+    connect(points, 1_000)
 }
 
-fn solve_1(input: &str, connection_count: usize) -> usize {
-    let points = parse(input);
-    let mut connections = build_connections(&points);
+fn connect(points: &Vec<Vector<u64>>, connection_count: usize) -> Result<usize, SolveError> {
+    let mut connections = build_connections(points);
+
+    if connections.len() <= connection_count {
+        return Err(SolveError::new(format!(
+            "expected more than {} connections, found {}",
+            connection_count,
+            connections.len()
+        )));
+    }
 
     let mut sets: Vec<Vec<usize>> = Vec::new();
     let mut point_to_set: Vec<Option<usize>> = vec![None; points.len()];
@@ -50,20 +63,30 @@ fn solve_1(input: &str, connection_count: usize) -> usize {
 
     let mut set_sizes = sets.iter().map(|s| s.len()).collect::<Vec<usize>>();
 
+    if set_sizes.len() <= 3 {
+        return Err(SolveError::new("expected more than three circuits"));
+    }
+
     let (top_set, _, _) = set_sizes.select_nth_unstable_by(3, |a, b| b.cmp(a));
-    top_set.iter().product::<usize>()
+    Ok(top_set.iter().product::<usize>())
 }
 
-fn parse(input: &str) -> Vec<Vector<u64>> {
+fn parse(input: &str) -> Result<Vec<Vector<u64>>, SolveError> {
     input
         .lines()
-        .map(|line| {
+        .enumerate()
+        .map(|(y, line)| {
             let mut bytes = line.bytes();
-            Vector {
-                x: bytes.next_number().unwrap(),
-                y: bytes.next_number().unwrap(),
-                z: bytes.next_number().unwrap(),
-            }
+            let mut next = || {
+                bytes.next_number().ok_or_else(|| {
+                    SolveError::at(y + 1, line.len() + 1, "expected three coordinates")
+                })
+            };
+            Ok(Vector {
+                x: next()?,
+                y: next()?,
+                z: next()?,
+            })
         })
         .collect()
 }
@@ -78,8 +101,14 @@ fn build_connections(points: &Vec<Vector<u64>>) -> Vec<Connection> {
     let mut connections = Vec::new();
     for i in 0..points.len() - 1 {
         for j in i + 1..points.len() {
+            let (a, b) = (points[i], points[j]);
+            let offset = Vector {
+                x: a.x.abs_diff(b.x),
+                y: a.y.abs_diff(b.y),
+                z: a.z.abs_diff(b.z),
+            };
             connections.push(Connection {
-                dist: (points[i] - points[j]).magnitude_relative(),
+                dist: offset.magnitude_relative(),
                 a: i,
                 b: j,
             });
@@ -96,18 +125,23 @@ mod part_1_tests {
 
     #[test_case(include_str!("_test.txt"), 40)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(solve_1(input, 10), expected.into());
+        assert_eq!(connect(&parse(input).unwrap(), 10), Ok(expected));
     }
 
     #[test_case(42840)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    let points = parse(_input);
-    let mut connections = build_connections(&points);
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_2(&parse(_input)?)?.into())
+}
+
+fn solve_2(points: &Vec<Vector<u64>>) -> Result<usize, SolveError> {
+    let mut connections = build_connections(points);
     connections.sort_unstable_by(|a, b| b.dist.cmp(&a.dist));
 
     let mut sets: Vec<Vec<usize>> = Vec::new();
@@ -115,45 +149,47 @@ pub fn part_2(_input: &str) -> Solution {
     let mut next_set = 0;
     let mut count = points.len();
 
-    loop {
-        if let Some(connection) = connections.pop() {
-            let opt_a = point_to_set[connection.a];
-            let opt_b = point_to_set[connection.b];
-
-            match (opt_a, opt_b) {
-                (None, None) => {
-                    point_to_set[connection.a] = Some(next_set);
-                    point_to_set[connection.b] = Some(next_set);
-                    sets.push(vec![connection.a, connection.b]);
-                    next_set += 1;
-                    count -= 1;
-                }
-                (None, Some(set)) => {
-                    point_to_set[connection.a] = Some(set);
-                    sets[set].push(connection.a);
-                    count -= 1;
-                }
-                (Some(set), None) => {
-                    point_to_set[connection.b] = Some(set);
-                    sets[set].push(connection.b);
-                    count -= 1;
-                }
-                (Some(set_a), Some(set_b)) => {
-                    if set_a != set_b {
-                        while let Some(p) = sets[set_b].pop() {
-                            point_to_set[p] = Some(set_a);
-                            sets[set_a].push(p);
-                        }
-                        count -= 1;
+    while let Some(connection) = connections.pop() {
+        let opt_a = point_to_set[connection.a];
+        let opt_b = point_to_set[connection.b];
+
+        match (opt_a, opt_b) {
+            (None, None) => {
+                point_to_set[connection.a] = Some(next_set);
+                point_to_set[connection.b] = Some(next_set);
+                sets.push(vec![connection.a, connection.b]);
+                next_set += 1;
+                count -= 1;
+            }
+            (None, Some(set)) => {
+                point_to_set[connection.a] = Some(set);
+                sets[set].push(connection.a);
+                count -= 1;
+            }
+            (Some(set), None) => {
+                point_to_set[connection.b] = Some(set);
+                sets[set].push(connection.b);
+                count -= 1;
+            }
+            (Some(set_a), Some(set_b)) => {
+                if set_a != set_b {
+                    while let Some(p) = sets[set_b].pop() {
+                        point_to_set[p] = Some(set_a);
+                        sets[set_a].push(p);
                     }
+                    count -= 1;
                 }
             }
+        }
 
-            if count == 1 {
-                return (points[connection.a].x as usize * points[connection.b].x as usize).into();
-            }
+        if count == 1 {
+            return Ok(points[connection.a].x as usize * points[connection.b].x as usize);
         }
     }
+
+    Err(SolveError::new(
+        "junction boxes never form a single circuit",
+    ))
 }
 
 #[cfg(test)]
@@ -163,11 +199,26 @@ mod part_2_tests {
 
     #[test_case(include_str!("_test.txt"), 25272)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(170629052)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{day::Part, differential::assert_matches, generate};
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1)]
+    #[test_case(part_2, reference::part_2)]
+    fn matches_reference(fast: Part, reference: Part) {
+        assert_matches(fast, reference, generate::junctions, 1000, 0..3);
     }
 }
@@ -0,0 +1,71 @@
+use shared::{Solution, error::SolveError};
+
+fn junctions(input: &str) -> Result<Vec<[u64; 3]>, SolveError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            let coordinates: Option<Vec<u64>> = line.split(',').map(|n| n.parse().ok()).collect();
+            coordinates
+                .and_then(|coordinates| coordinates.try_into().ok())
+                .ok_or_else(|| SolveError::at(y + 1, 1, "expected three coordinates"))
+        })
+        .collect()
+}
+
+/// Every pair of junction boxes, closest first.
+fn pairs(boxes: &[[u64; 3]]) -> Vec<(usize, usize)> {
+    let distance = |a: &[u64; 3], b: &[u64; 3]| -> u64 {
+        (0..3).map(|axis| a[axis].abs_diff(b[axis]).pow(2)).sum()
+    };
+    let mut pairs: Vec<(u64, usize, usize)> = (0..boxes.len())
+        .flat_map(|i| (i + 1..boxes.len()).map(move |j| (distance(&boxes[i], &boxes[j]), i, j)))
+        .collect();
+    pairs.sort();
+    pairs.into_iter().map(|(_, i, j)| (i, j)).collect()
+}
+
+/// Moves every box in `b`'s circuit into `a`'s.
+fn join(circuits: &mut [usize], a: usize, b: usize) {
+    let (from, to) = (circuits[b], circuits[a]);
+    circuits
+        .iter_mut()
+        .filter(|circuit| **circuit == from)
+        .for_each(|circuit| *circuit = to);
+}
+
+/// Joins the thousand closest pairs, then counts the boxes in every circuit.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    let boxes = junctions(input)?;
+    let mut circuits: Vec<usize> = (0..boxes.len()).collect();
+    for (a, b) in pairs(&boxes).into_iter().take(1_000) {
+        join(&mut circuits, a, b);
+    }
+
+    let mut sizes: Vec<usize> = (0..boxes.len())
+        .map(|circuit| circuits.iter().filter(|&&c| c == circuit).count())
+        .collect();
+    sizes.sort_by(|a, b| b.cmp(a));
+    Ok(sizes.iter().take(3).product::<usize>().into())
+}
+
+/// Joins the closest pairs until every box is in one circuit.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    let boxes = junctions(input)?;
+    let mut circuits: Vec<usize> = (0..boxes.len()).collect();
+    let mut count = boxes.len();
+    for (a, b) in pairs(&boxes) {
+        if circuits[a] == circuits[b] {
+            continue;
+        }
+        join(&mut circuits, a, b);
+        count -= 1;
+        if count == 1 {
+            return Ok(((boxes[a][0] * boxes[b][0]) as usize).into());
+        }
+    }
+
+    Err(SolveError::new(
+        "junction boxes never form a single circuit",
+    ))
+}
@@ -1,50 +1,76 @@
 #![feature(iter_advance_by)]
 use itertools::Itertools;
 use shared::{
-    parse::Parsable,
+    compress::{Compressed, SummedArea},
+    error::SolveError,
+    parse::cursor::Cursor,
     points::{iline::ILine, ipoint::IPoint},
     *,
 };
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(9, "Movie Theater", [part_1, part_2], parse => [solve_1, solve_2]);
 
-pub fn part_1(_input: &str) -> Solution {
-    parse(_input)
+pub mod reference;
+
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse(_input)?)?.into())
+}
+
+fn solve_1(points: &[IPoint]) -> Result<Option<usize>, SolveError> {
+    Ok(points
         .iter()
         .tuple_combinations()
         .map(|(a, b)| (a.x.abs_diff(b.x) + 1) * (a.y.abs_diff(b.y) + 1))
-        .max()
-        .into()
+        .max())
 }
 
-fn parse(input: &str) -> Vec<IPoint> {
-    input
-        .lines()
-        .map(|line| {
-            let mut bytes = line.bytes();
-            return IPoint {
-                x: bytes.next_number().unwrap(),
-                y: bytes.next_number().unwrap(),
-            };
-        })
-        .collect()
+fn parse(input: &str) -> Result<Vec<IPoint>, SolveError> {
+    let mut points = Vec::new();
+    let mut cursor = Cursor::new(input);
+
+    while !cursor.is_empty() {
+        let x = cursor.expect_number()?;
+        cursor.expect_byte(b',')?;
+        let y = cursor.expect_number()?;
+        points.push(IPoint { x, y });
+        if !cursor.is_empty() {
+            cursor.expect_byte(b'\n')?;
+        }
+    }
+
+    if points.is_empty() {
+        return Err(SolveError::new("expected at least one point"));
+    }
+
+    Ok(points)
 }
 
 #[cfg(test)]
 mod part_1_tests {
     use crate::*;
+    use shared::error::Position;
     use test_case::test_case;
 
     #[test_case(include_str!("_test.txt"), 50)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
+    }
+
+    #[test_case("1,2\n3x,4", 2, 2)]
+    #[test_case("1,2\n3,\n", 2, 3)]
+    #[test_case("1;2", 1, 2)]
+    fn invalid_input(input: &str, line: usize, column: usize) {
+        let error = part_1(input).unwrap_err();
+        assert_eq!(error.position, Some(Position::new(line, column)));
     }
 
     #[test_case(4749672288)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
@@ -54,50 +80,13 @@ struct Rectangle {
     area: usize,
 }
 
-struct TileLine<'a> {
-    line: &'a ILine,
-    height: isize,
-    under_is_inside: bool,
-}
-
-fn build_tile_lines(lines: &'_ Vec<ILine>, right_oriented: bool) -> Vec<TileLine<'_>> {
-    let mut tile_lines: Vec<TileLine> = lines
-        .iter()
-        .filter_map(|line| match line.from.x.cmp(&line.to.x) {
-            std::cmp::Ordering::Less => Some(TileLine {
-                line: line,
-                height: line.from.y,
-                under_is_inside: right_oriented,
-            }),
-            std::cmp::Ordering::Equal => None,
-            std::cmp::Ordering::Greater => Some(TileLine {
-                line: line,
-                height: line.from.y,
-                under_is_inside: !right_oriented,
-            }),
-        })
-        .collect();
-
-    tile_lines.sort_by(|a, b| b.height.cmp(&a.height));
-    tile_lines
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_2(&parse(_input)?)?.into())
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    let points = parse(_input);
-    
-    let mut lines: Vec<ILine> = points.windows(2).map(|p| ILine::new(p[0], p[1])).collect();
-    lines.push(ILine::new(
-        *points.last().unwrap(),
-        *points.first().unwrap(),
-    ));
-
-    let right_oriented = lines
-        .windows(2)
-        .map(|slice| slice[0].orient(&slice[1].to).signum())
-        .sum::<isize>()
-        > 0;
-
-    let tile_lines = build_tile_lines(&lines, right_oriented);
+fn solve_2(points: &[IPoint]) -> Result<usize, SolveError> {
+    let compressed = Compressed::new(points.iter().copied());
+    let tiles = SummedArea::new(&compressed.polygon(points));
 
     let mut rectangles: Vec<Rectangle> = points
         .iter()
@@ -111,80 +100,15 @@ pub fn part_2(_input: &str) -> Solution {
 
     rectangles.sort_by(|a, b| b.area.cmp(&a.area));
 
-    for rectangle in rectangles {
-        let c = IPoint {
-            x: rectangle.a.x,
-            y: rectangle.b.y,
-        };
-
-        let d = IPoint {
-            x: rectangle.b.x,
-            y: rectangle.a.y,
-        };
-
-        let center = IPoint {
-            x: rectangle.a.x.min(rectangle.b.x)
-                + (rectangle.a.x.abs_diff(rectangle.b.x) as isize / 2),
-            y: rectangle.a.y.min(rectangle.b.y)
-                + (rectangle.a.y.abs_diff(rectangle.b.y) as isize / 2),
-        };
-
-        if !point_is_inside(&center, &tile_lines)
-            || !point_is_inside(&c, &tile_lines)
-            || !point_is_inside(&d, &tile_lines)
-            || inner_rectangle_is_pierced(&rectangle, &lines)
-        {
-            continue;
-        }
-
-        return rectangle.area.into();
-    }
-
-    Solution::None
-}
-
-fn inner_rectangle_is_pierced(rectangle: &Rectangle, lines: &Vec<ILine>) -> bool {
-    let min_x = rectangle.a.x.min(rectangle.b.x) + 1;
-    let min_y = rectangle.a.y.min(rectangle.b.y) + 1;
-    let max_x = rectangle.a.x.max(rectangle.b.x) - 1;
-    let max_y = rectangle.a.y.max(rectangle.b.y) - 1;
-
-    let inner_corners = vec![
-        IPoint::new(min_x, min_y),
-        IPoint::new(min_x, max_y),
-        IPoint::new(max_x, max_y),
-        IPoint::new(max_x, min_y),
-        IPoint::new(min_x, min_y),
-    ];
-    for inner_line in inner_corners
-        .windows(2)
-        .map(|slice| ILine::new(slice[0], slice[1]))
-    {
-        for line in lines {
-            if line.crosses_straight(&inner_line) {
-                return true;
-            }
-        }
-    }
-
-    false
-}
-
-fn point_is_inside(point: &IPoint, tile_lines: &Vec<TileLine>) -> bool {
-    let trace = ILine::new(IPoint { x: point.x, y: -1 }, *point);
-    for tile_line in tile_lines {
-        if point.y >= tile_line.height && tile_line.line.crosses_straight(&trace) {
-            if point.y == tile_line.height {
-                return true;
-            }
-            return tile_line.under_is_inside;
-        }
-    }
-    false
+    rectangles
+        .iter()
+        .find(|rectangle| compressed.contains(&tiles, rectangle.a, rectangle.b))
+        .map(|rectangle| rectangle.area)
+        .ok_or_else(|| SolveError::new("no rectangle fits inside the polygon"))
 }
 
-pub fn validate(input: &str) {
-    let points = parse(input);
+pub fn validate(input: &str) -> Result<(), SolveError> {
+    let points = parse(input)?;
     for (a, b) in points.iter().tuple_combinations() {
         if a.x.abs_diff(b.x) == 1 {
             println!("Points are too close {} {}", a, b)
@@ -218,6 +142,7 @@ pub fn validate(input: &str) {
     println!("left: {} right: {}", left, right);
 
     println!("finished validating");
+    Ok(())
 }
 
 #[cfg(test)]
@@ -231,21 +156,41 @@ mod part_2_tests {
     #[test_case(include_str!("_alex.txt"), 39)]
     #[test_case(include_str!("_reddit.txt"), 30)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(1479665889)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
     }
 
     #[test_case(include_str!("_reddit.txt"))]
     fn validate_example(input: &str) {
-        validate(input);
+        validate(input).unwrap();
     }
 
     #[test]
+    #[ignore = "needs the puzzle input"]
     fn validate_input() {
-        validate(_INPUT);
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        validate(&input).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{differential::assert_matches, generate};
+
+    #[test]
+    fn part_1_matches_reference() {
+        assert_matches(part_1, reference::part_1, generate::polygon, 8, 0..100);
+    }
+
+    #[test]
+    fn part_2_matches_reference() {
+        assert_matches(part_2, reference::part_2, generate::polygon, 8, 0..100);
     }
 }
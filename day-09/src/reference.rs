@@ -0,0 +1,110 @@
+use shared::{Solution, error::SolveError};
+
+fn corners(input: &str) -> Result<Vec<(i64, i64)>, SolveError> {
+    if input.trim().is_empty() {
+        return Err(SolveError::new("expected at least one point"));
+    }
+
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            let coordinates = line
+                .split_once(',')
+                .and_then(|(a, b)| Some((a.trim().parse().ok()?, b.trim().parse().ok()?)));
+            coordinates.ok_or_else(|| SolveError::at(y + 1, 1, "expected two coordinates"))
+        })
+        .collect()
+}
+
+fn area(a: (i64, i64), b: (i64, i64)) -> usize {
+    ((a.0.abs_diff(b.0) + 1) * (a.1.abs_diff(b.1) + 1)) as usize
+}
+
+/// Tries every pair of corners.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    let corners = corners(input)?;
+    let largest = (0..corners.len())
+        .flat_map(|i| (i + 1..corners.len()).map(move |j| (i, j)))
+        .map(|(i, j)| area(corners[i], corners[j]))
+        .max();
+    Ok(largest.into())
+}
+
+/// Tries every pair of corners, checking every tile of the rectangle.
+///
+/// Tiles between two neighbouring corner coordinates all look the same, so one tile stands in
+/// for each such block: every corner coordinate is a block of its own and every gap between
+/// two of them is another.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    let corners = corners(input)?;
+    let edges: Vec<_> = (0..corners.len())
+        .map(|i| (corners[i], corners[(i + 1) % corners.len()]))
+        .collect();
+    if let Some((i, _)) = edges
+        .iter()
+        .enumerate()
+        .find(|(_, (a, b))| a.0 != b.0 && a.1 != b.1)
+    {
+        return Err(SolveError::at(
+            i + 1,
+            1,
+            "expected a row or column shared with the next corner",
+        ));
+    }
+
+    let on_edge = |(x, y): (i64, i64)| {
+        edges.iter().any(|&(a, b)| {
+            a.0.min(b.0) <= x && x <= a.0.max(b.0) && a.1.min(b.1) <= y && y <= a.1.max(b.1)
+        })
+    };
+    let inside = |(x, y): (i64, i64)| {
+        let crossings = edges
+            .iter()
+            .filter(|&&(a, b)| a.0 == b.0 && a.0 > x && a.1.min(b.1) <= y && y < a.1.max(b.1))
+            .count();
+        crossings % 2 == 1
+    };
+
+    let blocks = |coordinates: Vec<i64>| {
+        let mut coordinates = coordinates;
+        coordinates.sort();
+        coordinates.dedup();
+        let mut blocks = Vec::new();
+        for (i, &c) in coordinates.iter().enumerate() {
+            blocks.push(c);
+            if let Some(&next) = coordinates.get(i + 1)
+                && next > c + 1
+            {
+                blocks.push(c + 1);
+            }
+        }
+        blocks
+    };
+    let xs = blocks(corners.iter().map(|c| c.0).collect());
+    let ys = blocks(corners.iter().map(|c| c.1).collect());
+
+    let tiled: Vec<Vec<bool>> = xs
+        .iter()
+        .map(|&x| {
+            ys.iter()
+                .map(|&y| on_edge((x, y)) || inside((x, y)))
+                .collect()
+        })
+        .collect();
+    let block =
+        |c: i64, blocks: &[i64]| blocks.binary_search(&c).unwrap_or_else(|_| unreachable!());
+
+    let mut largest = 0;
+    for (i, &a) in corners.iter().enumerate() {
+        for &b in &corners[i + 1..] {
+            let (x1, x2) = (block(a.0.min(b.0), &xs), block(a.0.max(b.0), &xs));
+            let (y1, y2) = (block(a.1.min(b.1), &ys), block(a.1.max(b.1), &ys));
+            if (x1..=x2).all(|x| (y1..=y2).all(|y| tiled[x][y])) {
+                largest = largest.max(area(a, b));
+            }
+        }
+    }
+
+    Ok(largest.into())
+}
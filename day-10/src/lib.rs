@@ -2,26 +2,61 @@
 use core::num;
 use fxhash::FxHashMap;
 use itertools::Itertools;
-use shared::{parse::Parsable, *};
+use shared::{
+    error::SolveError,
+    parse::cursor::{Cursor, ParseError},
+    *,
+};
 use std::{
-    iter::from_fn,
     ops::{Add, Shl, Shr, Sub},
     u64,
 };
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(
+    10,
+    "Factory",
+    [part_1, part_2],
+    parse_1 => [solve_1],
+    parse_2 => [solve_2],
+);
 
-pub fn part_1(_input: &str) -> Solution {
-    _input
-        .lines()
-        .map(|line| {
-            let (goal, buttons) = parse_line_1(line);
-            solve_line_1(goal, &buttons)
-        })
-        .sum::<u32>()
-        .into()
+pub mod reference;
+
+/// Part 2 packs one counter into every ten bits of a `u128`.
+const COUNTERS: usize = 10;
+const MOST_JOLTAGE: u16 = (1 << 10) - 1;
+
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse_1(_input)?)?.into())
+}
+
+fn parse_1(input: &str) -> Result<Vec<(u16, Vec<u16>)>, SolveError> {
+    let mut machines = Vec::new();
+    let mut cursor = Cursor::new(input);
+
+    while !cursor.is_empty() {
+        let (goal, lights) = parse_lights(&mut cursor)?;
+        let buttons = parse_buttons(&mut cursor, lights)?
+            .iter()
+            .map(|button| button.iter().fold(0, |acc, &light| acc | 0b1 << light))
+            .collect();
+        parse_joltages(&mut cursor, lights, u16::MAX)?;
+        machines.push((goal, buttons));
+        if !cursor.is_empty() {
+            cursor.expect_byte(b'\n')?;
+        }
+    }
+
+    Ok(machines)
+}
+
+fn solve_1(machines: &[(u16, Vec<u16>)]) -> Result<u32, SolveError> {
+    Ok(machines
+        .iter()
+        .map(|(goal, buttons)| solve_line_1(*goal, buttons))
+        .sum())
 }
 
 fn solve_line_1(goal: u16, buttons: &Vec<u16>) -> u32 {
@@ -38,57 +73,99 @@ fn solve_line_1(goal: u16, buttons: &Vec<u16>) -> u32 {
         .unwrap_or(0)
 }
 
-fn parse_line_1(line: &str) -> (u16, Vec<u16>) {
-    let mut bytes = line.bytes();
-    let mut buttons = Vec::new();
-
-    let mut goal: u16 = 0;
-    let mut i = 0;
-    while let Some(next) = bytes.next() {
-        match next {
-            b']' => break,
-            b'#' => {
-                goal |= 0b1 << i;
-                i += 1
-            }
-            b'.' => i += 1,
-            _ => continue,
+/// The lights that should be on, as a mask, and how many lights there are.
+fn parse_lights(cursor: &mut Cursor) -> Result<(u16, usize), ParseError> {
+    cursor.expect_byte(b'[')?;
+    let mut goal = 0;
+    let mut lights = 0;
+    while lights < COUNTERS {
+        match cursor.peek() {
+            Some(b'#') => goal |= 0b1 << lights,
+            Some(b'.') => {}
+            _ => break,
         }
+        cursor.next();
+        lights += 1;
     }
+    cursor.expect_byte(b']')?;
+
+    Ok((goal, lights))
+}
 
-    let mut current = 0;
-    while let Some(next) = bytes.next() {
-        match next {
-            b'{' => break,
-            b')' => {
-                buttons.push(current);
-                current = 0;
+/// The lights each button is wired to.
+fn parse_buttons(cursor: &mut Cursor, lights: usize) -> Result<Vec<Vec<usize>>, ParseError> {
+    let mut buttons = Vec::new();
+    cursor.skip_spaces();
+    while cursor.peek() == Some(b'(') {
+        cursor.next();
+        let mut button = Vec::new();
+        loop {
+            let start = *cursor;
+            let light: usize = cursor.expect_number()?;
+            if light >= lights {
+                return Err(start.error(format!("a light below {}", lights)));
             }
-            b => {
-                if b.is_ascii_digit() {
-                    let x = b - b'0';
-                    current |= 0b1 << x;
-                }
+            button.push(light);
+            if cursor.peek() != Some(b',') {
+                break;
             }
+            cursor.next();
+        }
+        cursor.expect_byte(b')')?;
+        buttons.push(button);
+        cursor.skip_spaces();
+    }
+
+    Ok(buttons)
+}
+
+/// One joltage for every light, each at most `most`.
+fn parse_joltages(cursor: &mut Cursor, lights: usize, most: u16) -> Result<Vec<u16>, ParseError> {
+    let mut joltages = Vec::with_capacity(lights);
+    cursor.expect_byte(b'{')?;
+    loop {
+        let start = *cursor;
+        let joltage = cursor.expect_number()?;
+        if joltage > most {
+            return Err(start.error(format!("a joltage of at most {}", most)));
+        }
+        joltages.push(joltage);
+        if joltages.len() >= lights {
+            break;
         }
+        cursor.expect_byte(b',')?;
     }
+    cursor.expect_byte(b'}')?;
 
-    (goal, buttons)
+    Ok(joltages)
 }
 
 #[cfg(test)]
 mod part_1_tests {
     use crate::*;
+    use shared::error::Position;
     use test_case::test_case;
 
     #[test_case(include_str!("_test.txt"), 7)]
     fn example_input(input: &str, expected: u32) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
+    }
+
+    #[test_case("[.#] (0,2) {1,2}", 1, 9)]
+    #[test_case("[.#] (0;1) {1,2}", 1, 8)]
+    #[test_case("[.x] (0) {1}", 1, 3)]
+    #[test_case("[.#] (0) {1,x}", 1, 13)]
+    #[test_case("[#] (0) {1}\n[#] (0) 1}", 2, 9)]
+    fn invalid_input(input: &str, line: usize, column: usize) {
+        let error = part_1(input).unwrap_err();
+        assert_eq!(error.position, Some(Position::new(line, column)));
     }
 
     #[test_case(520)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: u32) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
@@ -97,41 +174,35 @@ struct Machine {
     total: u128,
 }
 
-fn parse_line_2(input: &str) -> Machine {
-    let mut bytes = input.bytes();
-    let mut buttons = Vec::new();
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_2(&parse_2(_input)?)?.into())
+}
 
-    let mut current: u128 = 0;
-    while let Some(next) = bytes.next() {
-        match next {
-            b'{' => break,
-            b')' => {
-                buttons.push(current);
-                current = 0;
-            }
-            b => {
-                if b.is_ascii_digit() {
-                    let x = b - b'0';
-                    current |= 0b1 << x * 10;
-                }
-            }
-        }
-    }
+fn parse_2(input: &str) -> Result<Vec<Machine>, SolveError> {
+    let mut machines = Vec::new();
+    let mut cursor = Cursor::new(input);
 
-    Machine {
-        buttons: buttons,
-        total: from_fn(|| Parsable::<u128>::next_number(&mut bytes))
+    while !cursor.is_empty() {
+        let (_, lights) = parse_lights(&mut cursor)?;
+        let buttons = parse_buttons(&mut cursor, lights)?
+            .iter()
+            .map(|button| button.iter().fold(0, |acc, &light| acc | 0b1 << light * 10))
+            .collect();
+        let total = parse_joltages(&mut cursor, lights, MOST_JOLTAGE)?
+            .iter()
             .enumerate()
-            .fold(0, |acc, (i, x)| acc | (x << i * 10)),
+            .fold(0, |acc, (i, &x)| acc | ((x as u128) << i * 10));
+        machines.push(Machine { buttons, total });
+        if !cursor.is_empty() {
+            cursor.expect_byte(b'\n')?;
+        }
     }
+
+    Ok(machines)
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    _input
-        .lines()
-        .map(|line| solve_line_2(&parse_line_2(line)))
-        .sum::<u64>()
-        .into()
+fn solve_2(machines: &[Machine]) -> Result<u64, SolveError> {
+    Ok(machines.iter().map(solve_line_2).sum())
 }
 
 fn solve_line_2(machine: &Machine) -> u64 {
@@ -179,7 +250,7 @@ fn solve_rec(
 
 #[inline]
 fn checked_sub(input: u128, other: u128) -> Option<u128> {
-    for i in 0..10 {
+    for i in 0..COUNTERS {
         let mask: u128 = 0b1111111111 << (i * 10);
         if (input & mask) < (other & mask) {
             return None;
@@ -190,7 +261,7 @@ fn checked_sub(input: u128, other: u128) -> Option<u128> {
 
 #[inline]
 fn is_even(input: u128) -> bool {
-    for i in 0..10 {
+    for i in 0..COUNTERS {
         if (input & (0b1 << (i * 10))) > 0 {
             return false;
         }
@@ -201,17 +272,56 @@ fn is_even(input: u128) -> bool {
 #[cfg(test)]
 mod part_2_tests {
     use crate::*;
+    use shared::error::Position;
     use test_case::test_case;
 
     #[test_case(include_str!("_test.txt"), 33)]
     #[test_case(include_str!("_short.txt"), 287)]
     #[test_case(include_str!("_panda.txt"), 19293)]
     fn example_input(input: &str, expected: u64) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
+    }
+
+    #[test_case("[.#] (0,2) {1,2}", 1, 9)]
+    #[test_case("[.#] (0) {1,x}", 1, 13)]
+    #[test_case("[#] (0) {1024}", 1, 10)]
+    #[test_case("[#] (0) {70000}", 1, 10)]
+    #[test_case("[##] (0) {1,2,3}", 1, 14)]
+    #[test_case("[##] (0) {1}", 1, 12)]
+    #[test_case("[###########] (0) {1,1,1,1,1,1,1,1,1,1,1}", 1, 12)]
+    #[test_case("[#] (0) {1}\n[#] (0) 1}", 2, 9)]
+    fn invalid_input(input: &str, line: usize, column: usize) {
+        let error = part_2(input).unwrap_err();
+        assert_eq!(error.position, Some(Position::new(line, column)));
+    }
+
+    #[test_case("[#] (0) {1023}", 1023)]
+    #[test_case("[##########] (0,9) {1,0,0,0,0,0,0,0,0,1}", 1)]
+    fn widest_machine(input: &str, expected: u64) {
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(20626)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: u64) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{
+        day::Part,
+        differential::assert_matches,
+        generate::{self, Generator},
+    };
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1, generate::machines)]
+    #[test_case(part_2, reference::part_2, generate::small_machines)]
+    fn matches_reference(fast: Part, reference: Part, generate: Generator) {
+        assert_matches(fast, reference, generate, 5, 0..100);
     }
 }
@@ -0,0 +1,99 @@
+use shared::{Solution, error::SolveError};
+
+struct Machine {
+    lights: Vec<bool>,
+    buttons: Vec<Vec<usize>>,
+    joltages: Vec<u32>,
+}
+
+fn machines(input: &str) -> Result<Vec<Machine>, SolveError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            machine(line).ok_or_else(|| SolveError::at(y + 1, 1, "expected a machine"))
+        })
+        .collect()
+}
+
+fn machine(line: &str) -> Option<Machine> {
+    let mut words = line.split_whitespace();
+    let lights = words.next()?.strip_prefix('[')?.strip_suffix(']')?;
+    let joltages = words.next_back()?.strip_prefix('{')?.strip_suffix('}')?;
+
+    let numbers =
+        |list: &str| -> Option<Vec<u32>> { list.split(',').map(|n| n.parse().ok()).collect() };
+    let buttons = words
+        .map(|button| {
+            let button = button.strip_prefix('(')?.strip_suffix(')')?;
+            Some(numbers(button)?.into_iter().map(|i| i as usize).collect())
+        })
+        .collect::<Option<Vec<Vec<usize>>>>()?;
+
+    Some(Machine {
+        lights: lights.chars().map(|c| c == '#').collect(),
+        buttons,
+        joltages: numbers(joltages)?,
+    })
+}
+
+/// Tries every set of buttons, pressing each at most once.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    let mut total = 0;
+
+    for machine in machines(input)? {
+        let fewest = (0u32..1 << machine.buttons.len())
+            .filter(|pressed| {
+                let mut lights = vec![false; machine.lights.len()];
+                for (i, button) in machine.buttons.iter().enumerate() {
+                    if pressed & (1 << i) != 0 {
+                        button.iter().for_each(|&light| lights[light] ^= true);
+                    }
+                }
+                lights == machine.lights
+            })
+            .map(|pressed| pressed.count_ones())
+            .min();
+        total += fewest.unwrap_or(0);
+    }
+
+    Ok(total.into())
+}
+
+/// Tries every number of presses for every button, up to where a counter would overshoot.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    let mut total = 0;
+
+    for machine in machines(input)? {
+        let mut counters = vec![0; machine.joltages.len()];
+        total += fewest_presses(&machine, 0, &mut counters).unwrap_or(0);
+    }
+
+    Ok(total.into())
+}
+
+/// The fewest presses of the buttons from `next` on that bring `counters` to the joltages.
+fn fewest_presses(machine: &Machine, next: usize, counters: &mut [u32]) -> Option<u64> {
+    let Some(button) = machine.buttons.get(next) else {
+        return (counters == machine.joltages.as_slice()).then_some(0);
+    };
+
+    let mut fewest = None;
+    for presses in 0.. {
+        if let Some(rest) = fewest_presses(machine, next + 1, counters) {
+            let total = presses + rest;
+            fewest = Some(fewest.map_or(total, |fewest: u64| fewest.min(total)));
+        }
+        if button.iter().any(|&c| counters[c] >= machine.joltages[c]) {
+            for &c in button {
+                counters[c] -= presses as u32;
+            }
+            break;
+        }
+        for &c in button {
+            counters[c] += 1;
+        }
+    }
+
+    fewest
+}
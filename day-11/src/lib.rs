@@ -1,24 +1,49 @@
 #![feature(iter_advance_by)]
 use std::iter::from_fn;
 
-use shared::{misc::translator::Translator, *};
+use shared::{error::SolveError, misc::translator::Translator, *};
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
+shared::day!(11, "Reactor", [part_1, part_2], parse => [solve_1, solve_2]);
 
-pub fn part_1(_input: &str) -> Solution {
-    let (graph, mut translator) = parse(_input);
-    count(YOU, OUT, &graph, &mut translator).into()
+pub mod reference;
+
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse(_input)?)?.into())
+}
+
+fn solve_1((graph, translator): &(Vec<Vec<u16>>, Translator<u16>)) -> Result<usize, SolveError> {
+    count(YOU, OUT, graph, translator)
 }
 
-fn count(from: u16, to: u16, graph: &Vec<Vec<u16>>, translator: &mut Translator<u16>) -> usize {
-    recursive_count(
-        translator.translate(from) as u16,
-        translator.translate(to) as u16,
+fn count(
+    from: u16,
+    to: u16,
+    graph: &Vec<Vec<u16>>,
+    translator: &Translator<u16>,
+) -> Result<usize, SolveError> {
+    let node = |id: u16| {
+        translator
+            .map
+            .get(&id)
+            .map(|&i| i as u16)
+            .ok_or_else(|| SolveError::new(format!("expected a device named '{}'", name(id))))
+    };
+
+    Ok(recursive_count(
+        node(from)?,
+        node(to)?,
         &graph,
         &mut vec![None; graph.len()],
-    )
+    ))
+}
+
+fn name(id: u16) -> String {
+    [id / 676, id / 26 % 26, id % 26]
+        .iter()
+        .map(|&c| (b'a' + c as u8) as char)
+        .collect()
 }
 
 fn recursive_count(
@@ -49,7 +74,7 @@ const SVR: u16 = 12731;
 const DAC: u16 = 2030;
 const FFT: u16 = 3529;
 
-fn parse(input: &str) -> (Vec<Vec<u16>>, Translator<u16>) {
+fn parse(input: &str) -> Result<(Vec<Vec<u16>>, Translator<u16>), SolveError> {
     let mut translator = Translator::new();
     let mut vec = Vec::new();
 
@@ -58,8 +83,9 @@ fn parse(input: &str) -> (Vec<Vec<u16>>, Translator<u16>) {
 
     let mut iters: Vec<_> = input.lines().map(|line| line.bytes()).collect();
 
-    for mut iter in iters.iter_mut() {
-        let start = next_id(&mut iter).unwrap();
+    for (y, mut iter) in iters.iter_mut().enumerate() {
+        let start =
+            next_id(&mut iter).ok_or_else(|| SolveError::at(y + 1, 1, "expected device name"))?;
         translator.translate(start);
     }
 
@@ -70,7 +96,7 @@ fn parse(input: &str) -> (Vec<Vec<u16>>, Translator<u16>) {
         );
     }
 
-    (vec, translator)
+    Ok((vec, translator))
 }
 
 fn next_id<T: Iterator<Item = u8>>(input: &mut T) -> Option<u16> {
@@ -98,29 +124,39 @@ mod part_1_tests {
 
     #[test_case(include_str!("_test1.txt"), 5)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
+    }
+
+    #[test]
+    fn missing_device() {
+        let error = part_1("aaa: out").unwrap_err();
+        assert_eq!(error.reason, "expected a device named 'you'");
     }
 
     #[test_case(431)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
     }
 }
 
-pub fn part_2(_input: &str) -> Solution {
-    let (graph, mut translator) = parse(_input);
+pub fn part_2(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_2(&parse(_input)?)?.into())
+}
 
-    let mut total = count(DAC, FFT, &graph, &mut translator);
+fn solve_2((graph, translator): &(Vec<Vec<u16>>, Translator<u16>)) -> Result<usize, SolveError> {
+    let mut total = count(DAC, FFT, graph, translator)?;
     if total == 0 {
-        total = count(SVR, FFT, &graph, &mut translator);
-        total *= count(FFT, DAC, &graph, &mut translator);
-        total *= count(DAC, OUT, &graph, &mut translator);
+        total = count(SVR, FFT, graph, translator)?;
+        total *= count(FFT, DAC, graph, translator)?;
+        total *= count(DAC, OUT, graph, translator)?;
     } else {
-        total *= count(SVR, DAC, &graph, &mut translator);
-        total *= count(FFT, OUT, &graph, &mut translator);
+        total *= count(SVR, DAC, graph, translator)?;
+        total *= count(FFT, OUT, graph, translator)?;
     }
 
-    total.into()
+    Ok(total)
 }
 
 #[cfg(test)]
@@ -130,11 +166,26 @@ mod part_2_tests {
 
     #[test_case(include_str!("_test2.txt"), 2)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_2(input), expected.into());
+        assert_eq!(part_2(input), Ok(expected.into()));
     }
 
     #[test_case(358458157650450)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_2(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_2(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{day::Part, differential::assert_matches, generate};
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1)]
+    #[test_case(part_2, reference::part_2)]
+    fn matches_reference(fast: Part, reference: Part) {
+        assert_matches(fast, reference, generate::devices, 15, 0..100);
     }
 }
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use shared::{Solution, error::SolveError};
+
+fn devices(input: &str) -> Result<HashMap<&str, Vec<&str>>, SolveError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            let (device, outputs) = line
+                .split_once(':')
+                .ok_or_else(|| SolveError::at(y + 1, 1, "expected device name"))?;
+            Ok((device, outputs.split_whitespace().collect()))
+        })
+        .collect()
+}
+
+/// Walks every path from `device` to `out`, calling `arrive` with the devices on it.
+fn walk<'a>(
+    devices: &HashMap<&'a str, Vec<&'a str>>,
+    device: &'a str,
+    path: &mut Vec<&'a str>,
+    arrive: &mut impl FnMut(&[&'a str]),
+) {
+    path.push(device);
+    if device == "out" {
+        arrive(path);
+    } else if let Some(outputs) = devices.get(device) {
+        for output in outputs {
+            walk(devices, output, path, arrive);
+        }
+    }
+    path.pop();
+}
+
+fn start<'a>(devices: &HashMap<&'a str, Vec<&'a str>>, name: &'a str) -> Result<(), SolveError> {
+    if devices.contains_key(name) {
+        Ok(())
+    } else {
+        Err(SolveError::new(format!(
+            "expected a device named '{}'",
+            name
+        )))
+    }
+}
+
+/// Walks every path from `you` to `out`.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    let devices = devices(input)?;
+    start(&devices, "you")?;
+
+    let mut paths = 0;
+    walk(&devices, "you", &mut Vec::new(), &mut |_| paths += 1);
+    Ok(paths.into())
+}
+
+/// Walks every path from `svr` to `out` and keeps those through both `dac` and `fft`.
+pub fn part_2(input: &str) -> Result<Solution, SolveError> {
+    let devices = devices(input)?;
+    start(&devices, "svr")?;
+
+    let mut paths = 0;
+    walk(&devices, "svr", &mut Vec::new(), &mut |path| {
+        if path.contains(&"dac") && path.contains(&"fft") {
+            paths += 1;
+        }
+    });
+    Ok(paths.into())
+}
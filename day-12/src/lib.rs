@@ -1,28 +1,140 @@
 #![feature(iter_advance_by)]
-use shared::{parse::Parsable, *};
+use shared::{
+    error::SolveError,
+    parse::cursor::{Cursor, ParseError},
+    *,
+};
 
 extern crate shared;
 
-pub const _INPUT: &'static str = include_str!("_input.txt");
-
-pub fn part_1(_input: &str) -> Solution {
-    let (shapes, problems) = parse(_input);
-
-    problems
-        .iter()
-        .map(|problem| {
-            let area = problem.height as u64 * problem.width as u64;
-            let shape_area = problem
-                .shape_counts
-                .iter()
-                .enumerate()
-                .fold(0, |acc, (i, count)| {
-                    acc + (*count as u64) * (shapes[i].area as u64)
-                });
-            return if shape_area > area { 0 } else { 1 };
-        })
-        .sum::<usize>()
-        .into()
+shared::day!(12, "Christmas Tree Farm", [part_1], parse => [solve_1]);
+
+pub mod reference;
+
+pub fn part_1(_input: &str) -> Result<Solution, SolveError> {
+    Ok(solve_1(&parse(_input)?)?.into())
+}
+
+fn solve_1((shapes, problems): &([Shape; 6], Vec<Problem>)) -> Result<usize, SolveError> {
+    let orientations: Vec<Vec<Orientation>> = shapes.iter().map(orientations).collect();
+    let mut fitting = 0;
+
+    for problem in problems {
+        let (mut width, mut height) = (problem.width as usize, problem.height as usize);
+        let shape_area: usize = problem
+            .shape_counts
+            .iter()
+            .zip(shapes)
+            .map(|(&count, shape)| count as usize * shape.area as usize)
+            .sum();
+        if shape_area > width * height {
+            continue;
+        }
+        let presents: Vec<usize> = (0..6)
+            .flat_map(|i| std::iter::repeat_n(i, problem.shape_counts[i] as usize))
+            .collect();
+        if presents.len() <= (width / 3) * (height / 3) {
+            fitting += 1;
+            continue;
+        }
+
+        if width > 64 {
+            (width, height) = (height, width);
+        }
+        if width > 64 {
+            return Err(SolveError::new(format!(
+                "a {}x{} region is too large to pack",
+                problem.height, problem.width
+            )));
+        }
+        let mut taken = vec![0; height];
+        if pack(&mut taken, width, &orientations, &presents, 0) {
+            fitting += 1;
+        }
+    }
+
+    Ok(fitting)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Orientation {
+    rows: [u8; 3],
+    width: usize,
+    height: usize,
+}
+
+/// Every distinct way to turn and flip a shape, moved up against the top left corner.
+fn orientations(shape: &Shape) -> Vec<Orientation> {
+    let mut cells: Vec<(usize, usize)> = (0..3)
+        .flat_map(|y| (0..3).map(move |x| (x, y)))
+        .filter(|&(x, y)| shape.mask[y] & (0b1 << x) != 0)
+        .collect();
+    let mut orientations = Vec::new();
+
+    for turn in 0..8 {
+        if turn == 4 {
+            cells.iter_mut().for_each(|(x, _)| *x = 2 - *x);
+        }
+        cells.iter_mut().for_each(|(x, y)| (*x, *y) = (2 - *y, *x));
+
+        let left = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let top = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let mut orientation = Orientation {
+            rows: [0; 3],
+            width: 0,
+            height: 0,
+        };
+        for &(x, y) in &cells {
+            orientation.rows[y - top] |= 0b1 << (x - left);
+            orientation.width = orientation.width.max(x - left + 1);
+            orientation.height = orientation.height.max(y - top + 1);
+        }
+        if !orientations.contains(&orientation) {
+            orientations.push(orientation);
+        }
+    }
+
+    orientations
+}
+
+/// Places the presents one after another, each copy of a shape on a later spot than the one
+/// before it so the same packing is not tried again in another order.
+fn pack(
+    taken: &mut [u64],
+    width: usize,
+    orientations: &[Vec<Orientation>],
+    presents: &[usize],
+    from: usize,
+) -> bool {
+    let Some((&shape, rest)) = presents.split_first() else {
+        return true;
+    };
+    let height = taken.len();
+
+    for spot in from..orientations[shape].len() * height * width {
+        let orientation = orientations[shape][spot / (height * width)];
+        let (top, left) = (spot / width % height, spot % width);
+        if left + orientation.width > width || top + orientation.height > height {
+            continue;
+        }
+        let rows = (0..orientation.height).map(|y| (top + y, (orientation.rows[y] as u64) << left));
+        if rows.clone().any(|(y, row)| taken[y] & row != 0) {
+            continue;
+        }
+
+        rows.clone().for_each(|(y, row)| taken[y] |= row);
+        let next = if rest.first() == Some(&shape) {
+            spot + 1
+        } else {
+            0
+        };
+        if pack(taken, width, orientations, rest, next) {
+            return true;
+        }
+        rows.for_each(|(y, row)| taken[y] &= !row);
+    }
+
+    false
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -38,67 +150,112 @@ struct Problem {
     shape_counts: [u8; 6],
 }
 
-fn parse(input: &str) -> ([Shape; 6], Vec<Problem>) {
+fn parse(input: &str) -> Result<([Shape; 6], Vec<Problem>), SolveError> {
     let mut shapes = [Shape {
         area: 0,
         mask: [0; 3],
     }; 6];
     let mut problems = Vec::new();
-    let mut lines = input.lines();
-
-    for i in 0..6 {
-        lines.next();
-
-        let mut area = 0;
-        for y in 0..3 {
-            if let Some(line) = lines.next() {
-                let mut bytes = line.bytes();
-                for x in 0..3 {
-                    if let Some(byte) = bytes.next()
-                        && byte == b'#'
-                    {
-                        shapes[i].mask[y] |= 0b1 << x;
-                        area += 1;
-                    }
-                }
-            }
-        }
+    let mut cursor = Cursor::new(input);
 
-        shapes[i].area = area;
-        lines.next();
-    }
+    while !cursor.is_empty() {
+        let start = cursor;
+        let number: u8 = cursor.expect_number()?;
+        if cursor.peek() == Some(b':') {
+            let shape = shapes
+                .get_mut(number as usize)
+                .ok_or_else(|| start.error("a shape below 6"))?;
+            cursor.expect_byte(b':')?;
+            cursor.expect_byte(b'\n')?;
+            *shape = parse_shape(&mut cursor)?;
+            cursor.expect_byte(b'\n')?;
+            continue;
+        }
 
-    for line in lines {
-        let mut bytes = line.bytes();
+        cursor.expect_byte(b'x')?;
+        let width = cursor.expect_number()?;
+        cursor.expect_byte(b':')?;
+        let mut shape_counts = [0; 6];
+        for count in shape_counts.iter_mut() {
+            cursor.expect_byte(b' ')?;
+            *count = cursor.expect_number()?;
+        }
         problems.push(Problem {
-            height: bytes.next_number().unwrap(),
-            width: bytes.next_number().unwrap(),
-            shape_counts: [
-                bytes.next_number().unwrap(),
-                bytes.next_number().unwrap(),
-                bytes.next_number().unwrap(),
-                bytes.next_number().unwrap(),
-                bytes.next_number().unwrap(),
-                bytes.next_number().unwrap(),
-            ],
+            height: number,
+            width,
+            shape_counts,
         });
+        if !cursor.is_empty() {
+            cursor.expect_byte(b'\n')?;
+        }
     }
 
-    (shapes, problems)
+    Ok((shapes, problems))
+}
+
+fn parse_shape(cursor: &mut Cursor) -> Result<Shape, ParseError> {
+    let mut shape = Shape {
+        area: 0,
+        mask: [0; 3],
+    };
+
+    for row in shape.mask.iter_mut() {
+        for x in 0..3 {
+            match cursor.peek() {
+                Some(b'#') => {
+                    *row |= 0b1 << x;
+                    shape.area += 1;
+                }
+                Some(b'.') => {}
+                _ => return Err(cursor.error("'#' or '.'")),
+            }
+            cursor.next();
+        }
+        cursor.expect_byte(b'\n')?;
+    }
+
+    Ok(shape)
 }
 
 #[cfg(test)]
 mod part_1_tests {
     use crate::*;
+    use shared::error::Position;
     use test_case::test_case;
 
     #[test_case(include_str!("_test.txt"), 0)]
+    #[test_case("0:\n##.\n##.\n#..\n\n3x2: 1 0 0 0 0 0\n", 1)]
+    #[test_case("0:\n###\n#..\n#..\n\n4x4: 2 0 0 0 0 0\n4x4: 3 0 0 0 0 0\n", 1)]
     fn example_input(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected.into());
+        assert_eq!(part_1(input), Ok(expected.into()));
+    }
+
+    #[test_case("0:\n###\n#x#\n###\n", 3, 2)]
+    #[test_case("6:\n###\n###\n###\n", 1, 1)]
+    #[test_case("4x4: 0 0 1 0 0\n", 1, 15)]
+    #[test_case("4x4: 0 0 0 0 0 0\n3y3: 1 0 0 0 0 0", 2, 2)]
+    #[test_case("4x4: 0 0 300 0 0 0", 1, 10)]
+    fn invalid_input(input: &str, line: usize, column: usize) {
+        let error = part_1(input).unwrap_err();
+        assert_eq!(error.position, Some(Position::new(line, column)));
     }
 
     #[test_case(433)]
+    #[ignore = "needs the puzzle input"]
     fn real_input(expected: usize) {
-        assert_eq!(part_1(_INPUT), expected.into());
+        let input = DAY.input.load().unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(part_1(&input), Ok(expected.into()));
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use crate::*;
+    use shared::{day::Part, differential::assert_matches, generate};
+    use test_case::test_case;
+
+    #[test_case(part_1, reference::part_1)]
+    fn matches_reference(fast: Part, reference: Part) {
+        assert_matches(fast, reference, generate::regions, 10, 0..100);
     }
 }
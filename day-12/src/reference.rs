@@ -0,0 +1,132 @@
+use shared::{Solution, error::SolveError};
+
+type Cells = Vec<(usize, usize)>;
+
+struct Region {
+    width: usize,
+    height: usize,
+    counts: Vec<usize>,
+}
+
+fn farm(input: &str) -> Result<(Vec<Cells>, Vec<Region>), SolveError> {
+    let mut shapes: Vec<Cells> = Vec::new();
+    let mut regions = Vec::new();
+    let mut row = None;
+
+    for (y, line) in input.lines().enumerate() {
+        if let Some((size, counts)) = line.split_once(": ") {
+            let region = size.split_once('x').and_then(|(width, height)| {
+                Some(Region {
+                    width: width.parse().ok()?,
+                    height: height.parse().ok()?,
+                    counts: counts
+                        .split(' ')
+                        .map(|count| count.parse().ok())
+                        .collect::<Option<_>>()?,
+                })
+            });
+            regions.push(region.ok_or_else(|| SolveError::at(y + 1, 1, "expected a region"))?);
+        } else if line.ends_with(':') {
+            shapes.push(Vec::new());
+            row = Some(0);
+        } else if line.is_empty() {
+            row = None;
+        } else if let (Some(r), Some(shape)) = (row.as_mut(), shapes.last_mut()) {
+            shape.extend(line.match_indices('#').map(|(x, _)| (x, *r)));
+            *r += 1;
+        } else {
+            return Err(SolveError::at(y + 1, 1, "expected a shape or a region"));
+        }
+    }
+
+    Ok((shapes, regions))
+}
+
+/// Every distinct way to turn and flip a shape, moved up against the top left corner.
+fn orientations(shape: &Cells) -> Vec<Cells> {
+    let mut orientations: Vec<Cells> = Vec::new();
+    let mut cells: Vec<(isize, isize)> = shape
+        .iter()
+        .map(|&(x, y)| (x as isize, y as isize))
+        .collect();
+
+    for turn in 0..8 {
+        if turn == 4 {
+            cells.iter_mut().for_each(|(x, _)| *x = -*x);
+        }
+        cells.iter_mut().for_each(|(x, y)| (*x, *y) = (-*y, *x));
+
+        let left = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let top = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let mut moved: Cells = cells
+            .iter()
+            .map(|&(x, y)| ((x - left) as usize, (y - top) as usize))
+            .collect();
+        moved.sort();
+        if !orientations.contains(&moved) {
+            orientations.push(moved);
+        }
+    }
+
+    orientations
+}
+
+/// Tries every spot and orientation for the first present, then the rest.
+fn place(taken: &mut Vec<Vec<bool>>, presents: &[&Vec<Cells>]) -> bool {
+    let Some((present, rest)) = presents.split_first() else {
+        return true;
+    };
+    let (width, height) = (taken[0].len(), taken.len());
+
+    for cells in present.iter() {
+        for top in 0..height {
+            for left in 0..width {
+                let spots: Option<Cells> = cells
+                    .iter()
+                    .map(|&(x, y)| (left + x, top + y))
+                    .map(|(x, y)| (x < width && y < height && !taken[y][x]).then_some((x, y)))
+                    .collect();
+                let Some(spots) = spots else {
+                    continue;
+                };
+
+                spots.iter().for_each(|&(x, y)| taken[y][x] = true);
+                if place(taken, rest) {
+                    return true;
+                }
+                spots.iter().for_each(|&(x, y)| taken[y][x] = false);
+            }
+        }
+    }
+
+    false
+}
+
+/// Looks for a way to place every present, once regions too small to cover all of them are
+/// ruled out.
+pub fn part_1(input: &str) -> Result<Solution, SolveError> {
+    let (shapes, regions) = farm(input)?;
+    let orientations: Vec<Vec<Cells>> = shapes.iter().map(orientations).collect();
+
+    let mut fitting = 0;
+    for region in regions {
+        let mut presents = Vec::new();
+        for (shape, &count) in region.counts.iter().enumerate() {
+            let orientations = orientations
+                .get(shape)
+                .ok_or_else(|| SolveError::new(format!("expected shape {}", shape)))?;
+            presents.extend((0..count).map(|_| orientations));
+        }
+
+        let covered: usize = presents.iter().map(|present| present[0].len()).sum();
+        if covered > region.width * region.height {
+            continue;
+        }
+        let mut taken = vec![vec![false; region.width]; region.height];
+        if place(&mut taken, &presents) {
+            fitting += 1;
+        }
+    }
+
+    Ok(fitting.into())
+}
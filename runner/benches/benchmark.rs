@@ -1,31 +1,94 @@
-use criterion::{Criterion, PlotConfiguration, black_box, criterion_group, criterion_main};
-use runner::day;
-use shared::{Solution, day_name};
+use criterion::{
+    BenchmarkId, Criterion, PlotConfiguration, black_box, criterion_group, criterion_main,
+};
+use runner::days;
+use shared::{Solution, generate};
 
 extern crate shared;
 
 fn criterion_benchmark(c: &mut Criterion) {
+    let days: Vec<_> = days()
+        .into_iter()
+        .filter_map(|day| day.input.load().ok().map(|input| (day, input)))
+        .collect();
+
     c.bench_function("Total: All Days", |b| {
         b.iter(|| {
-            for i in 1..=23 {
-                let (function, input, _) = day(i);
-                function(black_box(input));
+            for (day, input) in days.iter() {
+                day.run(black_box(input));
             }
         })
     });
 
+    let mut group = c.benchmark_group("Whole Day");
+    group
+        .plot_config(PlotConfiguration::default().summary_scale(criterion::AxisScale::Logarithmic));
+
+    for (day, input) in days.iter() {
+        group.bench_function(format!("{:02}: {}", day.number, day.title), |b| {
+            b.iter(|| day.run(black_box(input)))
+        });
+    }
+
+    group.finish();
+
     let mut group = c.benchmark_group("Individual");
     group
         .plot_config(PlotConfiguration::default().summary_scale(criterion::AxisScale::Logarithmic));
 
-    for i in 1..=23 {
-        let (function, input, name) = day(i);
-        if let Solution::None = function(input) {
-        } else {
-            let title = day_name((i + 1) / 2);
-            group.bench_function(&format!("{}: {}", name, title), |b| {
-                b.iter(|| function(black_box(input)))
+    for (day, input) in days.iter() {
+        for (part, function) in day.parts() {
+            if let Ok(Solution::None) | Err(_) = function(input) {
+            } else {
+                group.bench_function(format!("{}: {}", day.id(part), day.title), |b| {
+                    b.iter(|| function(black_box(input)))
+                });
+            }
+        }
+    }
+
+    group.finish();
+
+    let mut group = c.benchmark_group("Phases");
+    group
+        .plot_config(PlotConfiguration::default().summary_scale(criterion::AxisScale::Logarithmic));
+
+    for (day, input) in days.iter() {
+        for (part, parse, solve) in day.phases() {
+            let Ok(parsed) = parse(input) else {
+                continue;
+            };
+            if let Ok(Solution::None) | Err(_) = solve(parsed.as_ref()) {
+                continue;
+            }
+
+            group.bench_function(format!("{}: parse", day.id(part)), |b| {
+                b.iter(|| parse(black_box(input)))
             });
+            group.bench_function(format!("{}: solve", day.id(part)), |b| {
+                b.iter(|| solve(black_box(parsed.as_ref())))
+            });
+        }
+    }
+
+    group.finish();
+
+    let mut group = c.benchmark_group("Scaling");
+    group
+        .plot_config(PlotConfiguration::default().summary_scale(criterion::AxisScale::Logarithmic));
+
+    for day in runner::days() {
+        let Some(generate) = generate::for_day(day.number) else {
+            continue;
+        };
+
+        for size in [10, 100, 1_000] {
+            let input = generate(size, 0);
+            for (part, function) in day.parts() {
+                group.bench_with_input(BenchmarkId::new(day.id(part), size), &input, |b, input| {
+                    b.iter(|| function(black_box(input)))
+                });
+            }
         }
     }
 
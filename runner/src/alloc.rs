@@ -0,0 +1,201 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+pub const ENABLED: bool = cfg!(feature = "allocations");
+
+thread_local! {
+    static COUNT: Cell<usize> = const { Cell::new(0) };
+    static BYTES: Cell<usize> = const { Cell::new(0) };
+    static CURRENT: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+pub struct Counting;
+
+impl Counting {
+    fn grow(by: usize) {
+        let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+        let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + by));
+        let _ = CURRENT.try_with(|current| {
+            current.set(current.get() + by);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+        });
+    }
+
+    fn shrink(by: usize) {
+        let _ = CURRENT.try_with(|current| current.set(current.get().saturating_sub(by)));
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            Self::shrink(layout.size());
+            Self::grow(new_size);
+        }
+        new
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Allocations {
+    pub count: usize,
+    pub bytes: usize,
+    pub peak: usize,
+    pub held: usize,
+}
+
+impl Allocations {
+    /// Combines two measurements where `later` ran while everything `self` held was still live.
+    pub fn then(self, later: Self) -> Self {
+        Self {
+            count: self.count + later.count,
+            bytes: self.bytes + later.bytes,
+            peak: self.peak.max(self.held + later.peak),
+            held: self.held + later.held,
+        }
+    }
+}
+
+/// Counts what `f` allocates on the current thread, peak being the most heap it held at once
+/// and held what was still allocated when it returned.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    let count = COUNT.get();
+    let bytes = BYTES.get();
+    let current = CURRENT.get();
+    PEAK.set(current);
+
+    let result = f();
+
+    let allocations = Allocations {
+        count: COUNT.get() - count,
+        bytes: BYTES.get() - bytes,
+        peak: PEAK.get().saturating_sub(current),
+        held: CURRENT.get().saturating_sub(current),
+    };
+    (result, allocations)
+}
+
+/// Counts the allocations of the first timed call to a sampled function, the one right after
+/// its warmup, so no extra run is needed.
+pub struct Counter {
+    warmup: u32,
+    calls: Cell<u32>,
+    allocations: Cell<Option<Allocations>>,
+}
+
+impl Counter {
+    pub fn after(warmup: u32) -> Self {
+        Self {
+            warmup,
+            calls: Cell::new(0),
+            allocations: Cell::new(None),
+        }
+    }
+
+    pub fn call<T>(&self, f: impl FnOnce() -> T) -> T {
+        let call = self.calls.replace(self.calls.get() + 1);
+        if !ENABLED || call != self.warmup {
+            return f();
+        }
+
+        let (result, allocations) = measure(f);
+        self.allocations.set(Some(allocations));
+        result
+    }
+
+    pub fn allocations(&self) -> Option<Allocations> {
+        self.allocations.get()
+    }
+}
+
+#[cfg(test)]
+mod alloc_tests {
+    use super::*;
+
+    #[test]
+    fn measure_counts_allocations_on_this_thread() {
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let (_, allocations) = measure(|| unsafe {
+            let a = Counting.alloc(layout);
+            let b = Counting.alloc_zeroed(layout);
+            Counting.dealloc(a, layout);
+            let b = Counting.realloc(b, layout, 256);
+            Counting.dealloc(b, Layout::from_size_align(256, 8).unwrap());
+        });
+
+        assert_eq!(
+            allocations,
+            Allocations {
+                count: 3,
+                bytes: 384,
+                peak: 256,
+                held: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn measure_reports_what_is_still_held() {
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let (kept, allocations) = measure(|| unsafe {
+            let kept = Counting.alloc(layout);
+            let dropped = Counting.alloc(layout);
+            Counting.dealloc(dropped, layout);
+            kept
+        });
+        unsafe { Counting.dealloc(kept, layout) };
+
+        assert_eq!(allocations.peak, 128);
+        assert_eq!(allocations.held, 64);
+    }
+
+    #[test]
+    fn then_stacks_the_later_peak_on_what_is_held() {
+        let parse = Allocations {
+            count: 1,
+            bytes: 30,
+            peak: 30,
+            held: 10,
+        };
+        let solve = Allocations {
+            count: 2,
+            bytes: 25,
+            peak: 25,
+            held: 0,
+        };
+        assert_eq!(
+            parse.then(solve),
+            Allocations {
+                count: 3,
+                bytes: 55,
+                peak: 35,
+                held: 10,
+            }
+        );
+    }
+}
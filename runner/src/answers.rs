@@ -0,0 +1,210 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use shared::{Solution, Verdict};
+
+pub const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+
+pub struct Answers {
+    pub path: PathBuf,
+    answers: BTreeMap<String, String>,
+    recorded: usize,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let answers = match fs::read_to_string(path) {
+            Ok(text) => parse(&text).map_err(|error| format!("{}: {}", path.display(), error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(format!("unable to read {}: {}", path.display(), error)),
+        };
+
+        Ok(Self {
+            path: path.to_owned(),
+            answers,
+            recorded: 0,
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.answers.get(id).map(|answer| answer.as_str())
+    }
+
+    pub fn check(&self, id: &str, solution: &Solution) -> Verdict {
+        match self.get(id) {
+            Some(expected) if solution.parse_like(expected).as_ref() == Some(solution) => {
+                Verdict::Correct
+            }
+            Some(expected) => Verdict::Wrong(expected.to_owned()),
+            None => Verdict::Unknown,
+        }
+    }
+
+    pub fn record(&mut self, id: &str, solution: &Solution) -> bool {
+        if let Solution::None = solution {
+            return false;
+        }
+
+        if self.answers.contains_key(id) {
+            return false;
+        }
+
+        self.answers.insert(id.to_owned(), solution.to_string());
+        self.recorded += 1;
+        true
+    }
+
+    pub fn recorded(&self) -> usize {
+        self.recorded
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(&self.path, self.to_string())
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (id, answer) in self.answers.iter() {
+            writeln!(f, "{} = {}", quote(id), quote(answer))?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn parse(text: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut answers = BTreeMap::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("line {}: expected 'key = value'", i + 1))?;
+
+        let key = unquote(key.trim()).map_err(|error| format!("line {}: {}", i + 1, error))?;
+        let value = unquote(value.trim()).map_err(|error| format!("line {}: {}", i + 1, error))?;
+        answers.insert(key, value);
+    }
+
+    Ok(answers)
+}
+
+pub(crate) fn quote(value: &str) -> String {
+    let mut str = String::with_capacity(value.len() + 2);
+    str.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => str.push_str("\\\""),
+            '\\' => str.push_str("\\\\"),
+            '\n' => str.push_str("\\n"),
+            c => str.push(c),
+        }
+    }
+    str.push('"');
+    str
+}
+
+fn unquote(value: &str) -> Result<String, String> {
+    let Some(inner) = value.strip_prefix('"') else {
+        return Ok(value.to_owned());
+    };
+
+    let inner = inner
+        .strip_suffix('"')
+        .ok_or_else(|| format!("unterminated string {}", value))?;
+
+    let mut str = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            str.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('"') => str.push('"'),
+            Some('\\') => str.push('\\'),
+            Some('n') => str.push('\n'),
+            other => return Err(format!("unsupported escape \\{}", other.unwrap_or(' '))),
+        }
+    }
+
+    Ok(str)
+}
+
+#[cfg(test)]
+mod answers_tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn answers(text: &str) -> Answers {
+        Answers {
+            path: PathBuf::new(),
+            answers: parse(text).unwrap(),
+            recorded: 0,
+        }
+    }
+
+    #[test_case(Solution::Usize(1023), Verdict::Correct)]
+    #[test_case(Solution::U64(1023), Verdict::Correct)]
+    #[test_case(Solution::I128(1023), Verdict::Correct)]
+    #[test_case(Solution::Str("1023".to_owned()), Verdict::Correct)]
+    #[test_case(Solution::Usize(1024), Verdict::Wrong("1023".to_owned()))]
+    fn check_known(solution: Solution, expected: Verdict) {
+        let answers = answers("# comment\n\"01.1\" = \"1023\"\n");
+        assert_eq!(answers.check("01.1", &solution), expected);
+    }
+
+    #[test_case(Solution::I64(-7), "-7", Verdict::Correct)]
+    #[test_case(Solution::U64(7), "-7", Verdict::Wrong("-7".to_owned()))]
+    #[test_case(Solution::from(vec![1u8, 2]), "1,2", Verdict::Correct)]
+    #[test_case(Solution::from(vec![1u8, 2]), "1,2,3", Verdict::Wrong("1,2,3".to_owned()))]
+    #[test_case(Solution::from("1,2"), "1,2", Verdict::Correct)]
+    fn check_same_variant(solution: Solution, answer: &str, expected: Verdict) {
+        let answers = answers(&format!("\"01.1\" = \"{}\"", answer));
+        assert_eq!(answers.check("01.1", &solution), expected);
+    }
+
+    #[test]
+    fn check_unknown() {
+        let answers = answers("01.1 = 1023");
+        assert_eq!(answers.check("01.2", &Solution::Usize(1)), Verdict::Unknown);
+        assert_eq!(
+            answers.check("01.1", &Solution::Usize(1023)),
+            Verdict::Correct
+        );
+    }
+
+    #[test]
+    fn record_only_new_answers() {
+        let mut answers = answers("\"01.1\" = \"1023\"");
+        assert!(!answers.record("01.1", &Solution::Usize(1)));
+        assert!(!answers.record("02.1", &Solution::None));
+        assert!(answers.record("01.2", &Solution::Str("a \"b\"\nc".to_owned())));
+        assert_eq!(answers.recorded(), 1);
+        assert_eq!(
+            answers.to_string(),
+            "\"01.1\" = \"1023\"\n\"01.2\" = \"a \\\"b\\\"\\nc\"\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = "\"01.1\" = \"1023\"\n\"01.2\" = \"a \\\"b\\\"\\nc\"\n";
+        assert_eq!(answers(text).to_string(), text);
+    }
+
+    #[test_case("01.1")]
+    #[test_case("\"01.1\" = \"1023")]
+    #[test_case("\"01.1\" = \"\\t\"")]
+    fn parse_invalid(text: &str) {
+        assert!(parse(text).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::answers::{parse, quote};
+
+pub const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../baseline.toml");
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Comparison {
+    pub baseline: Duration,
+    pub current: Duration,
+    pub change: Ordering,
+}
+
+impl Comparison {
+    pub fn ratio(&self) -> f64 {
+        self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.change {
+            Ordering::Less => "faster",
+            Ordering::Equal => "same",
+            Ordering::Greater => "slower",
+        }
+    }
+}
+
+pub struct Baselines {
+    pub path: PathBuf,
+    timings: BTreeMap<String, Duration>,
+}
+
+impl Baselines {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let entries = match fs::read_to_string(path) {
+            Ok(text) => parse(&text).map_err(|error| format!("{}: {}", path.display(), error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(format!("unable to read {}: {}", path.display(), error)),
+        };
+
+        let timings = entries
+            .into_iter()
+            .map(|(id, nanos)| {
+                nanos
+                    .parse()
+                    .map(|nanos| (id, Duration::from_nanos(nanos)))
+                    .map_err(|_| {
+                        format!(
+                            "{}: '{}' is not a number of nanoseconds",
+                            path.display(),
+                            nanos
+                        )
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            path: path.to_owned(),
+            timings,
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<Duration> {
+        self.timings.get(id).copied()
+    }
+
+    /// `None` when there is no baseline for `id`, or it is zero and so can't be compared with.
+    pub fn compare(&self, id: &str, current: Duration, threshold: f64) -> Option<Comparison> {
+        let baseline = self.get(id).filter(|baseline| !baseline.is_zero())?;
+        let mut comparison = Comparison {
+            baseline,
+            current,
+            change: Ordering::Equal,
+        };
+
+        let ratio = comparison.ratio();
+        if ratio > threshold {
+            comparison.change = Ordering::Greater;
+        } else if ratio < -threshold {
+            comparison.change = Ordering::Less;
+        }
+
+        Some(comparison)
+    }
+
+    pub fn set(&mut self, id: &str, time: Duration) {
+        self.timings.insert(id.to_owned(), time);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(&self.path, self.to_string())
+    }
+}
+
+impl std::fmt::Display for Baselines {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (id, time) in self.timings.iter() {
+            writeln!(f, "{} = {}", quote(id), time.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod baseline_tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn baselines(text: &str) -> Baselines {
+        let timings = parse(text)
+            .unwrap()
+            .into_iter()
+            .map(|(id, nanos)| (id, Duration::from_nanos(nanos.parse().unwrap())))
+            .collect();
+        Baselines {
+            path: PathBuf::new(),
+            timings,
+        }
+    }
+
+    #[test_case(1000, Ordering::Equal)]
+    #[test_case(1090, Ordering::Equal)]
+    #[test_case(1200, Ordering::Greater)]
+    #[test_case(800, Ordering::Less)]
+    fn compare(current: u64, expected: Ordering) {
+        let baselines = baselines("\"01.1\" = 1000\n");
+        let comparison = baselines
+            .compare("01.1", Duration::from_nanos(current), 0.1)
+            .unwrap();
+        assert_eq!(comparison.change, expected);
+        assert_eq!(comparison.baseline, Duration::from_nanos(1000));
+    }
+
+    #[test]
+    fn compare_unknown() {
+        let baselines = baselines("");
+        assert_eq!(
+            baselines.compare("01.1", Duration::from_nanos(1), 0.1),
+            None
+        );
+    }
+
+    #[test]
+    fn compare_zero_baseline() {
+        let baselines = baselines("\"01.1\" = 0\n");
+        assert_eq!(
+            baselines.compare("01.1", Duration::from_nanos(5), 0.1),
+            None
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut baselines = baselines("\"01.2\" = 42\n");
+        baselines.set("01.1", Duration::from_micros(3));
+        assert_eq!(baselines.to_string(), "\"01.1\" = 3000\n\"01.2\" = 42\n");
+    }
+}
@@ -0,0 +1,32 @@
+use std::{env, process};
+
+use shared::generate;
+
+const USAGE: &str = "\
+Usage: generate <day> [size] [seed]
+
+Prints a random input for any day from 1 to 12 (default size: 100, seed: 0).";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let number = |i: usize, default: u64| match args.get(i) {
+        Some(arg) => arg.parse().unwrap_or_else(|_| {
+            eprintln!("'{}' is not a number\n\n{}", arg, USAGE);
+            process::exit(2);
+        }),
+        None => default,
+    };
+
+    if args.is_empty() || args.len() > 3 {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    }
+
+    let (day, size, seed) = (number(0, 0), number(1, 100) as usize, number(2, 0));
+    let Some(generate) = generate::for_day(day as u32) else {
+        eprintln!("no generator for day {}\n\n{}", day, USAGE);
+        process::exit(2);
+    };
+
+    print!("{}", generate(size, seed));
+}
@@ -0,0 +1,387 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Read},
+    path::PathBuf,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use shared::input::{self, Input, InputError};
+
+use crate::{answers, baseline, output::Format};
+
+pub const USAGE: &str = "\
+Usage: runner [SELECTION]... [OPTIONS]
+
+Selection:
+  9            both parts of day 9
+  9.2          part 2 of day 9
+  3..7         days 3 to 6
+  3..=7        days 3 to 7
+  3..          day 3 and onwards
+
+Options:
+  --input-dir <dir> read inputs as <dir>/XX.txt
+  --input <file>    read every selected part from <file>, without checking answers
+  --stdin           read every selected part from standard input, without checking answers
+  --repeat <n>      run each part n times and report the average time
+  --runs <n>        time each part n times and report min, median, mean and stddev
+  --warmup <n>      run each part n times before timing it
+  --phases          time parsing and solving separately
+  --whole-day       parse each day once, share it between its parts and time the day
+  --parallel        solve days on a thread pool and report the wall-clock time as well
+  --format <fmt>    print results as text, json (one object per line) or csv
+  --answers <file>  check results against <file> (default: answers.toml)
+  --record          add answers that are not yet known to the answers file
+  --compare         compare timings against the baseline file
+  --save-baseline   store the timings of this run in the baseline file
+  --baseline <file> use <file> as the baseline file (default: baseline.toml)
+  --threshold <pct> treat changes within pct percent as the same (default: 10)
+  --budget <time>   fail when all selected parts take longer than time (default: 1s, not enforced)
+  --day-budget [<day>=]<time>
+                    fail when a day takes longer than time, for every day or only <day>
+  -h, --help        print this message
+
+Times are given as a number with an optional unit of ns, us, ms or s (default: ms).
+Build with --features allocations to also report allocations and peak heap per part.";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Selector {
+    Day(u32),
+    Part(u32, u32),
+    Range(u32, u32),
+}
+
+impl Selector {
+    pub fn parse(arg: &str) -> Result<Self, String> {
+        let number = |s: &str| {
+            s.parse::<u32>()
+                .map_err(|_| format!("'{}' is not a valid selection", arg))
+        };
+
+        if let Some((from, to)) = arg.split_once("..") {
+            let from = if from.is_empty() { 1 } else { number(from)? };
+            let to = if let Some(to) = to.strip_prefix('=') {
+                number(to)?
+            } else if to.is_empty() {
+                u32::MAX
+            } else {
+                number(to)?.saturating_sub(1)
+            };
+            return Ok(Self::Range(from, to));
+        }
+
+        if let Some((day, part)) = arg.split_once('.') {
+            return Ok(Self::Part(number(day)?, number(part)?));
+        }
+
+        Ok(Self::Day(number(arg)?))
+    }
+
+    pub fn matches(&self, day: u32, part: usize) -> bool {
+        match *self {
+            Self::Day(d) => d == day,
+            Self::Part(d, p) => d == day && p as usize == part,
+            Self::Range(from, to) => from <= day && day <= to,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Source {
+    Default,
+    Dir(PathBuf),
+    File(PathBuf),
+    Stdin,
+}
+
+static STDIN: OnceLock<String> = OnceLock::new();
+
+impl Source {
+    /// Whether this reads the puzzle inputs, which are the only ones the answers are for.
+    pub fn is_puzzle(&self) -> bool {
+        matches!(self, Self::Default | Self::Dir(_))
+    }
+
+    pub fn load(&self, input: &Input) -> Result<String, InputError> {
+        match self {
+            Self::Default => input.load(),
+            Self::Dir(dir) => input.load_from(Some(dir)),
+            Self::File(path) => input::read(path),
+            Self::Stdin => {
+                if let Some(text) = STDIN.get() {
+                    return Ok(text.clone());
+                }
+
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(|error| InputError::Unreadable(PathBuf::from("stdin"), error))?;
+                Ok(STDIN.get_or_init(|| text).clone())
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Options {
+    pub selectors: Vec<Selector>,
+    pub source: Source,
+    pub repeat: u32,
+    pub runs: u32,
+    pub warmup: u32,
+    pub phases: bool,
+    pub whole_day: bool,
+    pub parallel: bool,
+    pub format: Format,
+    pub answers: PathBuf,
+    pub record: bool,
+    pub baseline: PathBuf,
+    pub compare: bool,
+    pub save_baseline: bool,
+    pub threshold: f64,
+    pub budget: Option<Duration>,
+    pub day_budget: Option<Duration>,
+    pub day_budgets: BTreeMap<u32, Duration>,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            selectors: Vec::new(),
+            source: Source::Default,
+            repeat: 1,
+            runs: 1,
+            warmup: 0,
+            phases: false,
+            whole_day: false,
+            parallel: false,
+            format: Format::Text,
+            answers: PathBuf::from(answers::DEFAULT_PATH),
+            record: false,
+            baseline: PathBuf::from(baseline::DEFAULT_PATH),
+            compare: false,
+            save_baseline: false,
+            threshold: 0.1,
+            budget: None,
+            day_budget: None,
+            day_budgets: BTreeMap::new(),
+            help: false,
+        }
+    }
+}
+
+impl Options {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("{} expects a value", name))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => options.help = true,
+                "--input-dir" => options.set_source(Source::Dir(value(&arg)?.into()))?,
+                "--input" => options.set_source(Source::File(value(&arg)?.into()))?,
+                "--stdin" => options.set_source(Source::Stdin)?,
+                "--repeat" => {
+                    options.repeat = match value(&arg)?.parse() {
+                        Ok(0) | Err(_) => return Err("--repeat expects a positive number".into()),
+                        Ok(n) => n,
+                    }
+                }
+                "--runs" => {
+                    options.runs = match value(&arg)?.parse() {
+                        Ok(0) | Err(_) => return Err("--runs expects a positive number".into()),
+                        Ok(n) => n,
+                    }
+                }
+                "--warmup" => {
+                    options.warmup = value(&arg)?
+                        .parse()
+                        .map_err(|_| "--warmup expects a number".to_owned())?
+                }
+                "--phases" => options.phases = true,
+                "--whole-day" => options.whole_day = true,
+                "--parallel" => options.parallel = true,
+                "--format" => options.format = Format::parse(&value(&arg)?)?,
+                "--answers" => options.answers = value(&arg)?.into(),
+                "--record" => options.record = true,
+                "--compare" => options.compare = true,
+                "--save-baseline" => options.save_baseline = true,
+                "--baseline" => options.baseline = value(&arg)?.into(),
+                "--threshold" => {
+                    options.threshold = match value(&arg)?.parse::<f64>() {
+                        Ok(percent) if percent >= 0.0 => percent / 100.0,
+                        _ => return Err("--threshold expects a non-negative percentage".into()),
+                    }
+                }
+                "--budget" => options.budget = Some(parse_duration(&arg, &value(&arg)?)?),
+                "--day-budget" => {
+                    let value = value(&arg)?;
+                    if let Some((day, time)) = value.split_once('=') {
+                        let day = day
+                            .parse()
+                            .map_err(|_| format!("'{}' is not a valid day", day))?;
+                        options.day_budgets.insert(day, parse_duration(&arg, time)?);
+                    } else {
+                        options.day_budget = Some(parse_duration(&arg, &value)?);
+                    }
+                }
+                _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+                _ => options.selectors.push(Selector::parse(&arg)?),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn set_source(&mut self, source: Source) -> Result<(), String> {
+        if self.source != Source::Default {
+            return Err("only one of --input-dir, --input and --stdin can be given".into());
+        }
+        self.source = source;
+        Ok(())
+    }
+
+    pub fn selects(&self, day: u32, part: usize) -> bool {
+        self.selectors.is_empty() || self.selectors.iter().any(|s| s.matches(day, part))
+    }
+
+    pub fn day_budget(&self, day: u32) -> Option<Duration> {
+        self.day_budgets.get(&day).copied().or(self.day_budget)
+    }
+}
+
+fn parse_duration(name: &str, value: &str) -> Result<Duration, String> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let scale = match unit {
+        "ns" => 1e-9,
+        "us" | "µs" => 1e-6,
+        "" | "ms" => 1e-3,
+        "s" => 1.0,
+        _ => return Err(format!("{} expects a time such as 250ms or 1s", name)),
+    };
+
+    number
+        .parse::<f64>()
+        .ok()
+        .and_then(|number| Duration::try_from_secs_f64(number * scale).ok())
+        .ok_or_else(|| format!("{} expects a time such as 250ms or 1s", name))
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("9", Selector::Day(9))]
+    #[test_case("9.2", Selector::Part(9, 2))]
+    #[test_case("3..7", Selector::Range(3, 6))]
+    #[test_case("3..=7", Selector::Range(3, 7))]
+    #[test_case("3..", Selector::Range(3, u32::MAX))]
+    #[test_case("..3", Selector::Range(1, 2))]
+    fn selector_parse(arg: &str, expected: Selector) {
+        assert_eq!(Selector::parse(arg), Ok(expected));
+    }
+
+    #[test_case("nine")]
+    #[test_case("9.x")]
+    #[test_case("3..y")]
+    fn selector_parse_invalid(arg: &str) {
+        assert!(Selector::parse(arg).is_err());
+    }
+
+    #[test]
+    fn options_parse() {
+        let args = [
+            "9.2",
+            "3..5",
+            "--input",
+            "file.txt",
+            "--repeat",
+            "10",
+            "--format",
+            "csv",
+            "--runs",
+            "7",
+            "--warmup",
+            "0",
+            "--phases",
+            "--parallel",
+            "--compare",
+            "--threshold",
+            "25",
+            "--budget",
+            "1.5s",
+            "--day-budget",
+            "50",
+            "--day-budget",
+            "8=200ms",
+        ];
+        let options = Options::parse(args.map(String::from)).unwrap();
+
+        assert_eq!(
+            options.selectors,
+            vec![Selector::Part(9, 2), Selector::Range(3, 4)]
+        );
+        assert_eq!(options.source, Source::File("file.txt".into()));
+        assert_eq!(options.repeat, 10);
+        assert_eq!(options.runs, 7);
+        assert_eq!(options.warmup, 0);
+        assert!(options.phases);
+        assert!(options.parallel);
+        assert!(options.compare);
+        assert_eq!(options.threshold, 0.25);
+        assert_eq!(options.budget, Some(Duration::from_millis(1500)));
+        assert_eq!(options.day_budget(3), Some(Duration::from_millis(50)));
+        assert_eq!(options.day_budget(8), Some(Duration::from_millis(200)));
+        assert_eq!(options.format, Format::Csv);
+        assert!(options.selects(9, 2));
+        assert!(options.selects(4, 1));
+        assert!(!options.selects(9, 1));
+        assert!(!options.selects(5, 1));
+    }
+
+    #[test_case(Source::Default, true)]
+    #[test_case(Source::Dir("inputs".into()), true)]
+    #[test_case(Source::File("generated.txt".into()), false)]
+    #[test_case(Source::Stdin, false)]
+    fn source_is_puzzle(source: Source, expected: bool) {
+        assert_eq!(source.is_puzzle(), expected);
+    }
+
+    #[test_case(&["--stdin", "--input", "file.txt"])]
+    #[test_case(&["--repeat", "0"])]
+    #[test_case(&["--repeat"])]
+    #[test_case(&["--runs", "0"])]
+    #[test_case(&["--warmup", "-1"])]
+    #[test_case(&["--threshold", "-5"])]
+    #[test_case(&["--budget", "1h"])]
+    #[test_case(&["--budget", "-1s"])]
+    #[test_case(&["--budget", "1e30s"])]
+    #[test_case(&["--budget", "1000000000000000000000000000000s"])]
+    #[test_case(&["--day-budget", "x=5ms"])]
+    #[test_case(&["--format", "xml"])]
+    #[test_case(&["--unknown"])]
+    fn options_parse_invalid(args: &[&str]) {
+        assert!(Options::parse(args.iter().map(|s| s.to_string())).is_err());
+    }
+
+    #[test_case("250", Duration::from_millis(250))]
+    #[test_case("250ms", Duration::from_millis(250))]
+    #[test_case("1.5s", Duration::from_millis(1500))]
+    #[test_case("40us", Duration::from_micros(40))]
+    #[test_case("900ns", Duration::from_nanos(900))]
+    fn duration_parse(value: &str, expected: Duration) {
+        assert_eq!(parse_duration("--budget", value), Ok(expected));
+    }
+}
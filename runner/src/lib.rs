@@ -1,30 +1,74 @@
-use shared::Solution;
-
-pub fn day(index: u32) -> &'static (fn(&str) -> Solution, &'static str, &'static str) {
-    match index {
-        1 => &(day_01::part_1, day_01::_INPUT, "01.1"),
-        2 => &(day_01::part_2, day_01::_INPUT, "01.2"),
-        3 => &(day_02::part_1, day_02::_INPUT, "02.1"),
-        4 => &(day_02::part_2, day_02::_INPUT, "02.2"),
-        5 => &(day_03::part_1, day_03::_INPUT, "03.1"),
-        6 => &(day_03::part_2, day_03::_INPUT, "03.2"),
-        7 => &(day_04::part_1, day_04::_INPUT, "04.1"),
-        8 => &(day_04::part_2, day_04::_INPUT, "04.2"),
-        9 => &(day_05::part_1, day_05::_INPUT, "05.1"),
-        10 => &(day_05::part_2, day_05::_INPUT, "05.2"),
-        11 => &(day_06::part_1, day_06::_INPUT, "06.1"),
-        12 => &(day_06::part_2, day_06::_INPUT, "06.2"),
-        13 => &(day_07::part_1, day_07::_INPUT, "07.1"),
-        14 => &(day_07::part_2, day_07::_INPUT, "07.2"),
-        15 => &(day_08::part_1, day_08::_INPUT, "08.1"),
-        16 => &(day_08::part_2, day_08::_INPUT, "08.2"),
-        17 => &(day_09::part_1, day_09::_INPUT, "09.1"),
-        18 => &(day_09::part_2, day_09::_INPUT, "09.2"),
-        19 => &(day_10::part_1, day_10::_INPUT, "10.1"),
-        20 => &(day_10::part_2, day_10::_INPUT, "10.2"),
-        21 => &(day_11::part_1, day_11::_INPUT, "11.1"),
-        22 => &(day_11::part_2, day_11::_INPUT, "11.2"),
-        23 => &(day_12::part_1, day_12::_INPUT, "12.1"),
-        _ => panic!("Index out of bounds"),
+use shared::day::Day;
+
+extern crate day_01;
+extern crate day_02;
+extern crate day_03;
+extern crate day_04;
+extern crate day_05;
+extern crate day_06;
+extern crate day_07;
+extern crate day_08;
+extern crate day_09;
+extern crate day_10;
+extern crate day_11;
+extern crate day_12;
+
+pub fn days() -> Vec<&'static Day> {
+    shared::day::days()
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use crate::*;
+    use shared::generate;
+
+    #[test]
+    fn every_day_is_registered_once() {
+        let numbers: Vec<u32> = days().iter().map(|day| day.number).collect();
+        assert_eq!(numbers, (1..=12).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn every_day_has_parts() {
+        for day in days() {
+            assert!(!day.parts.is_empty(), "Day {} has no parts", day.number);
+            assert!(!day.title.is_empty(), "Day {} has no title", day.number);
+        }
+    }
+
+    #[test]
+    fn every_part_has_phases() {
+        for day in days() {
+            assert_eq!(
+                day.phases().count(),
+                day.parts.len(),
+                "Day {} has a different number of phases and parts",
+                day.number
+            );
+        }
+    }
+
+    #[test]
+    fn every_phase_agrees_with_its_part() {
+        for day in days() {
+            let generate = generate::for_day(day.number).expect("every day has a generator");
+            for seed in 0..3 {
+                let input = generate(50, seed);
+                let parts: Vec<_> = day.parts().map(|(_, part)| part(&input)).collect();
+                assert_eq!(
+                    day.run(&input),
+                    parts,
+                    "Day {} phases and parts disagree on seed {}",
+                    day.number,
+                    seed
+                );
+            }
+        }
     }
 }
+
+pub mod alloc;
+pub mod answers;
+pub mod baseline;
+pub mod cli;
+pub mod output;
@@ -1,25 +1,391 @@
-use std::time::Duration;
-
-use runner::day;
-use shared::{parse::Parsable, *};
-use std::panic;
+use std::{
+    any::Any,
+    cmp::Ordering,
+    env, io,
+    panic::{self, AssertUnwindSafe},
+    process,
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+    thread,
+    time::{Duration, Instant},
+};
 
+use runner::{
+    alloc::Counter,
+    answers::Answers,
+    baseline::Baselines,
+    cli::{Options, USAGE},
+    days,
+    output::{Measurement, Reporter},
+};
+use shared::{
+    day::{Day, Part},
+    error::SolveError,
+    input::InputError,
+    timing::{Split, Timing},
+    *,
+};
 extern crate shared;
 
-fn main() {
+#[cfg(feature = "allocations")]
+#[global_allocator]
+static ALLOCATOR: runner::alloc::Counting = runner::alloc::Counting;
+
+fn main() -> io::Result<()> {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if options.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let mut answers = match Answers::load(&options.answers) {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(2);
+        }
+    };
+
+    let mut baselines = if options.compare || options.save_baseline {
+        match Baselines::load(&options.baseline) {
+            Ok(baselines) => Some(baselines),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(2);
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut reporter = Reporter::new(options.format, io::stdout().lock())?;
     let mut time: Duration = Duration::new(0, 0);
-    for i in 1..=23 {
-        let (function, input, id) = day(i);
-        let day = id.bytes().next_number().unwrap();
+    let mut wrong = 0;
+    let mut failures = 0;
+    let mut regressions = 0;
+    let mut over_budget = 0;
+
+    let jobs: Vec<_> = days()
+        .into_iter()
+        .filter(|day| {
+            day.parts()
+                .any(|(part, _)| options.selects(day.number, part))
+        })
+        .map(|day| (day, options.source.load(&day.input)))
+        .collect();
+
+    let started = Instant::now();
+    let mut solved = if options.parallel {
+        parallel(&jobs, &options)
+    } else {
+        Vec::new()
+    }
+    .into_iter();
+
+    for (day, input) in &jobs {
+        let input = match input {
+            Ok(input) => input,
+            Err(error) => {
+                reporter.missing(day, error)?;
+                continue;
+            }
+        };
+
+        let Solved { outcomes, timing } = match solved.next() {
+            Some(solved) => solved,
+            None => solve_day(day, input, &options),
+        };
+
+        let mut day_time = Duration::ZERO;
+        if let Some(timing) = timing {
+            day_time = timing.median;
+            reporter.day(day, &timing)?;
+        }
+
+        for (part, outcome) in outcomes {
+            match outcome {
+                Ok((Ok(solution), measurement)) => {
+                    let timing = measurement.timing;
+                    if !options.whole_day && !matches!(solution, Solution::None) {
+                        day_time += timing.median;
+                    }
+
+                    let id = day.id(part);
+                    let verdict = if options.source.is_puzzle() {
+                        answers.check(&id, &solution)
+                    } else {
+                        Verdict::Unknown
+                    };
+                    if let Verdict::Wrong(_) = verdict {
+                        wrong += 1;
+                    }
+                    if options.record && options.source.is_puzzle() {
+                        answers.record(&id, &solution);
+                    }
+
+                    reporter.solved(day, part, &solution, &verdict, &measurement)?;
+
+                    if let Some(baselines) = baselines.as_mut()
+                        && !matches!(solution, Solution::None)
+                    {
+                        if options.compare
+                            && let Some(comparison) =
+                                baselines.compare(&id, timing.median, options.threshold)
+                        {
+                            if comparison.change == Ordering::Greater {
+                                regressions += 1;
+                            }
+                            reporter.compared(day, part, &comparison)?;
+                        }
+                        if options.save_baseline {
+                            baselines.set(&id, timing.median);
+                        }
+                    }
+                }
+                Ok((Err(error), _)) => {
+                    failures += 1;
+                    reporter.failed(day, part, &error.on_day(day.number))?
+                }
+                Err(payload) => {
+                    failures += 1;
+                    let error = SolveError::new(format!("panicked: {}", panic_message(&payload)));
+                    reporter.failed(day, part, &error.on_day(day.number))?
+                }
+            }
+        }
+
+        time += day_time;
+        if let Some(budget) = options.day_budget(day.number)
+            && day_time > budget
+        {
+            over_budget += 1;
+            reporter.over_budget(day, day_time, budget)?;
+        }
+    }
+    if options.parallel {
+        reporter.wall(started.elapsed())?;
+    }
+    reporter.total(time, options.budget.unwrap_or(BUDGET))?;
+    if options.budget.is_some_and(|budget| time > budget) {
+        over_budget += 1;
+    }
+
+    if answers.recorded() > 0 {
+        answers.save()?;
+        eprintln!(
+            "Recorded {} answers in {}",
+            answers.recorded(),
+            answers.path.display()
+        );
+    }
+
+    if let Some(baselines) = baselines
+        && options.save_baseline
+    {
+        baselines.save()?;
+        eprintln!("Saved baseline in {}", baselines.path.display());
+    }
+
+    if wrong > 0 || failures > 0 || regressions > 0 || over_budget > 0 {
+        process::exit(1);
+    }
+
+    Ok(())
+}
 
-        let result = panic::catch_unwind(|| execute(function, input, id, day_name(day)));
+type Outcome = thread::Result<(Result<Solution, SolveError>, Measurement)>;
+
+struct Solved {
+    outcomes: Vec<(usize, Outcome)>,
+    timing: Option<Timing>,
+}
+
+fn solve_day(day: &Day, input: &str, options: &Options) -> Solved {
+    if options.whole_day {
+        let (outcomes, timing) = whole_day(day, input, options);
+        return Solved {
+            outcomes,
+            timing: Some(timing),
+        };
+    }
+
+    let outcomes = day
+        .parts()
+        .filter(|(part, _)| options.selects(day.number, *part))
+        .map(|(part, function)| (part, single_part(day, part, function, input, options)))
+        .collect();
+    Solved {
+        outcomes,
+        timing: None,
+    }
+}
+
+fn parallel(jobs: &[(&Day, Result<String, InputError>)], options: &Options) -> Vec<Solved> {
+    let inputs: Vec<_> = jobs
+        .iter()
+        .filter_map(|(day, input)| Some((*day, input.as_ref().ok()?)))
+        .collect();
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    let next = AtomicUsize::new(0);
+
+    let mut solved: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers.min(inputs.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut solved = Vec::new();
+                    while let Some((day, input)) = inputs.get(next.fetch_add(1, Relaxed)) {
+                        solved.push((day.number, solve_day(day, input, options)));
+                    }
+                    solved
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("worker panicked"))
+            .collect()
+    });
+
+    solved.sort_by_key(|(number, _)| *number);
+    solved.into_iter().map(|(_, solved)| solved).collect()
+}
 
-        match result {
-            Ok(duration) => time += duration,
-            Err(_) => {
-                println!("Day {} failed", day);
+fn single_part(day: &Day, part: usize, function: &Part, input: &str, options: &Options) -> Outcome {
+    panic::catch_unwind(|| {
+        if options.phases
+            && let Some((parse, solve)) = day.phase(part)
+        {
+            let parsing = Counter::after(options.warmup);
+            let solving = Counter::after(options.warmup);
+            let (result, split) = sample_phases(
+                &|input| parsing.call(|| parse(input)),
+                &|parsed| solving.call(|| solve(parsed)),
+                input,
+                options.repeat,
+                options.warmup,
+                options.runs,
+            );
+            let measurement = Measurement {
+                timing: split.total(),
+                split: Some(split),
+                allocations: parsing
+                    .allocations()
+                    .zip(solving.allocations())
+                    .map(|(parse, solve)| parse.then(solve)),
+            };
+            return (result, measurement);
+        }
+
+        let counter = Counter::after(options.warmup);
+        let (result, timing) = sample(
+            &|input| counter.call(|| function(input)),
+            input,
+            options.repeat,
+            options.warmup,
+            options.runs,
+        );
+        let measurement = Measurement {
+            timing,
+            split: None,
+            allocations: counter.allocations(),
+        };
+        (result, measurement)
+    })
+}
+
+fn whole_day(day: &Day, input: &str, options: &Options) -> (Vec<(usize, Outcome)>, Timing) {
+    let mut outcomes = Vec::new();
+    let mut total = Timing::single(Duration::ZERO);
+    let mut part = 0;
+
+    for stage in day.stages {
+        let parts = part + 1..=part + stage.solve.len();
+        part += stage.solve.len();
+        if !parts.clone().any(|part| options.selects(day.number, part)) {
+            continue;
+        }
+
+        let parsing = Counter::after(options.warmup);
+        let parsed = panic::catch_unwind(AssertUnwindSafe(|| {
+            sample(
+                &|input| parsing.call(|| (stage.parse)(input)),
+                input,
+                options.repeat,
+                options.warmup,
+                options.runs,
+            )
+        }));
+        let selected = parts
+            .clone()
+            .filter(|&part| options.selects(day.number, part));
+        let (parsed, parse_timing) = match parsed {
+            Ok((Ok(parsed), timing)) => (parsed, timing),
+            Ok((Err(error), timing)) => {
+                outcomes.extend(selected.map(|part| {
+                    let measurement = Measurement {
+                        timing,
+                        split: None,
+                        allocations: None,
+                    };
+                    (part, Ok((Err(error.clone()), measurement)))
+                }));
+                continue;
+            }
+            Err(payload) => {
+                let message = panic_message(&payload).to_owned();
+                outcomes.extend(selected.map(|part| (part, Err(Box::new(message.clone()) as _))));
+                continue;
             }
+        };
+        total = total + parse_timing;
+
+        for (part, solve) in parts.zip(stage.solve) {
+            if !options.selects(day.number, part) {
+                continue;
+            }
+
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                let solving = Counter::after(options.warmup);
+                let (result, solve_timing) = sample(
+                    &|_| solving.call(|| solve(parsed.as_ref())),
+                    input,
+                    options.repeat,
+                    options.warmup,
+                    options.runs,
+                );
+                let measurement = Measurement {
+                    timing: solve_timing,
+                    split: Some(Split::new(parse_timing, solve_timing)),
+                    allocations: parsing
+                        .allocations()
+                        .zip(solving.allocations())
+                        .map(|(parse, solve)| parse.then(solve)),
+                };
+                (result, measurement)
+            }));
+            if let Ok((_, measurement)) = &outcome {
+                total = total + measurement.timing;
+            }
+            outcomes.push((part, outcome));
         }
     }
-    total(time);
+
+    (outcomes, total)
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown cause"
+    }
 }
@@ -0,0 +1,580 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use crate::{alloc::Allocations, baseline::Comparison};
+use shared::{
+    Solution, Verdict,
+    day::Day,
+    error::SolveError,
+    input::InputError,
+    timing::{Split, Timing},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!(
+                "unknown format '{}', expected text, json or csv",
+                value
+            )),
+        }
+    }
+}
+
+const COLUMNS: [&str; 22] = [
+    "record",
+    "day",
+    "part",
+    "title",
+    "status",
+    "answer",
+    "answer_type",
+    "verdict",
+    "expected",
+    "error",
+    "elapsed_ns",
+    "runs",
+    "min_ns",
+    "mean_ns",
+    "stddev_ns",
+    "parse_ns",
+    "solve_ns",
+    "baseline_ns",
+    "budget_ns",
+    "allocations",
+    "allocated_bytes",
+    "peak_bytes",
+];
+
+struct Row<'a> {
+    record: &'static str,
+    day: Option<u32>,
+    part: Option<usize>,
+    title: &'a str,
+    status: &'static str,
+    answer: Option<String>,
+    answer_type: Option<&'static str>,
+    verdict: Option<&'a Verdict>,
+    error: Option<String>,
+    elapsed: Option<Duration>,
+    timing: Option<&'a Timing>,
+    split: Option<&'a Split>,
+    baseline: Option<Duration>,
+    budget: Option<Duration>,
+    allocations: Option<&'a Allocations>,
+}
+
+impl Row<'_> {
+    fn values(&self) -> [Option<Value>; 22] {
+        [
+            Some(Value::Str(self.record.to_owned())),
+            self.day.map(|day| Value::Number(day as u128)),
+            self.part.map(|part| Value::Number(part as u128)),
+            Some(Value::Str(self.title.to_owned())),
+            Some(Value::Str(self.status.to_owned())),
+            self.answer.clone().map(Value::Str),
+            self.answer_type.map(|kind| Value::Str(kind.to_owned())),
+            self.verdict
+                .map(|verdict| Value::Str(verdict.name().to_owned())),
+            match self.verdict {
+                Some(Verdict::Wrong(expected)) => Some(Value::Str(expected.clone())),
+                _ => None,
+            },
+            self.error.clone().map(Value::Str),
+            self.elapsed.map(|time| Value::Number(time.as_nanos())),
+            self.timing.map(|timing| Value::Number(timing.runs as u128)),
+            self.timing
+                .map(|timing| Value::Number(timing.min.as_nanos())),
+            self.timing
+                .map(|timing| Value::Number(timing.mean.as_nanos())),
+            self.timing
+                .map(|timing| Value::Number(timing.stddev.as_nanos())),
+            self.split
+                .map(|split| Value::Number(split.parse.median.as_nanos())),
+            self.split
+                .map(|split| Value::Number(split.solve.median.as_nanos())),
+            self.baseline.map(|time| Value::Number(time.as_nanos())),
+            self.budget.map(|time| Value::Number(time.as_nanos())),
+            self.allocations
+                .map(|allocations| Value::Number(allocations.count as u128)),
+            self.allocations
+                .map(|allocations| Value::Number(allocations.bytes as u128)),
+            self.allocations
+                .map(|allocations| Value::Number(allocations.peak as u128)),
+        ]
+    }
+}
+
+enum Value {
+    Str(String),
+    Number(u128),
+}
+
+pub struct Measurement {
+    pub timing: Timing,
+    pub split: Option<Split>,
+    pub allocations: Option<Allocations>,
+}
+
+pub struct Reporter<W: Write> {
+    format: Format,
+    out: W,
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(format: Format, mut out: W) -> io::Result<Self> {
+        if format == Format::Csv {
+            writeln!(out, "{}", COLUMNS.join(","))?;
+        }
+        Ok(Self { format, out })
+    }
+
+    pub fn solved(
+        &mut self,
+        day: &Day,
+        part: usize,
+        result: &Solution,
+        verdict: &Verdict,
+        measurement: &Measurement,
+    ) -> io::Result<()> {
+        let Measurement {
+            timing,
+            split,
+            allocations,
+        } = measurement;
+
+        if let Solution::None = result {
+            if self.format == Format::Text {
+                shared::unsolved(&day.id(part), day.title);
+                return Ok(());
+            }
+
+            return self.write(Row {
+                record: "part",
+                day: Some(day.number),
+                part: Some(part),
+                title: day.title,
+                status: "unsolved",
+                answer: None,
+                answer_type: None,
+                verdict: None,
+                error: None,
+                elapsed: Some(timing.median),
+                timing: Some(timing),
+                split: split.as_ref(),
+                baseline: None,
+                budget: None,
+                allocations: allocations.as_ref(),
+            });
+        }
+
+        if self.format == Format::Text {
+            shared::report(result, timing.median, &day.id(part), day.title, verdict);
+            if timing.runs > 1 {
+                shared::statistics(timing);
+            }
+            if let Some(split) = split {
+                shared::phases(split);
+            }
+            if let Some(allocations) = allocations {
+                shared::allocations(allocations.count, allocations.bytes, allocations.peak);
+            }
+            return Ok(());
+        }
+
+        self.write(Row {
+            record: "part",
+            day: Some(day.number),
+            part: Some(part),
+            title: day.title,
+            status: "solved",
+            answer: Some(result.to_string()),
+            answer_type: Some(result.kind()),
+            verdict: Some(verdict),
+            error: None,
+            elapsed: Some(timing.median),
+            timing: Some(timing),
+            split: split.as_ref(),
+            baseline: None,
+            budget: None,
+            allocations: allocations.as_ref(),
+        })
+    }
+
+    pub fn missing(&mut self, day: &Day, error: &InputError) -> io::Result<()> {
+        if self.format == Format::Text {
+            shared::missing(&format!("{:02}", day.number), day.title, error);
+            return Ok(());
+        }
+
+        self.write(Row {
+            record: "day",
+            day: Some(day.number),
+            part: None,
+            title: day.title,
+            status: "missing",
+            answer: None,
+            answer_type: None,
+            verdict: None,
+            error: Some(error.to_string()),
+            elapsed: None,
+            timing: None,
+            split: None,
+            baseline: None,
+            budget: None,
+            allocations: None,
+        })
+    }
+
+    pub fn failed(&mut self, day: &Day, part: usize, error: &SolveError) -> io::Result<()> {
+        if self.format == Format::Text {
+            shared::failed(&day.id(part), day.title, error);
+            return Ok(());
+        }
+
+        self.write(Row {
+            record: "part",
+            day: Some(day.number),
+            part: Some(part),
+            title: day.title,
+            status: "failed",
+            answer: None,
+            answer_type: None,
+            verdict: None,
+            error: Some(error.to_string()),
+            elapsed: None,
+            timing: None,
+            split: None,
+            baseline: None,
+            budget: None,
+            allocations: None,
+        })
+    }
+
+    pub fn day(&mut self, day: &Day, timing: &Timing) -> io::Result<()> {
+        if self.format == Format::Text {
+            shared::whole_day(&format!("{:02}", day.number), day.title, timing.median);
+            return Ok(());
+        }
+
+        self.write(Row {
+            record: "day",
+            day: Some(day.number),
+            part: None,
+            title: day.title,
+            status: "solved",
+            answer: None,
+            answer_type: None,
+            verdict: None,
+            error: None,
+            elapsed: Some(timing.median),
+            timing: Some(timing),
+            split: None,
+            baseline: None,
+            budget: None,
+            allocations: None,
+        })
+    }
+
+    pub fn compared(&mut self, day: &Day, part: usize, comparison: &Comparison) -> io::Result<()> {
+        if self.format == Format::Text {
+            shared::compared(comparison.baseline, comparison.ratio(), comparison.change);
+            return Ok(());
+        }
+
+        self.write(Row {
+            record: "baseline",
+            day: Some(day.number),
+            part: Some(part),
+            title: day.title,
+            status: comparison.name(),
+            answer: None,
+            answer_type: None,
+            verdict: None,
+            error: None,
+            elapsed: Some(comparison.current),
+            timing: None,
+            split: None,
+            baseline: Some(comparison.baseline),
+            budget: None,
+            allocations: None,
+        })
+    }
+
+    pub fn over_budget(&mut self, day: &Day, time: Duration, budget: Duration) -> io::Result<()> {
+        if self.format == Format::Text {
+            shared::over_budget(&format!("{:02}", day.number), day.title, time, budget);
+            return Ok(());
+        }
+
+        self.write(Row {
+            record: "budget",
+            day: Some(day.number),
+            part: None,
+            title: day.title,
+            status: "over",
+            answer: None,
+            answer_type: None,
+            verdict: None,
+            error: None,
+            elapsed: Some(time),
+            timing: None,
+            split: None,
+            baseline: None,
+            budget: Some(budget),
+            allocations: None,
+        })
+    }
+
+    pub fn wall(&mut self, time: Duration) -> io::Result<()> {
+        if self.format == Format::Text {
+            shared::wall(time);
+            return Ok(());
+        }
+
+        self.write(Row {
+            record: "wall",
+            day: None,
+            part: None,
+            title: "Wall clock",
+            status: "solved",
+            answer: None,
+            answer_type: None,
+            verdict: None,
+            error: None,
+            elapsed: Some(time),
+            timing: None,
+            split: None,
+            baseline: None,
+            budget: None,
+            allocations: None,
+        })
+    }
+
+    pub fn total(&mut self, time: Duration, budget: Duration) -> io::Result<()> {
+        if self.format == Format::Text {
+            shared::total(time, budget);
+            return Ok(());
+        }
+
+        self.write(Row {
+            record: "total",
+            day: None,
+            part: None,
+            title: "Total",
+            status: if time > budget { "over" } else { "solved" },
+            answer: None,
+            answer_type: None,
+            verdict: None,
+            error: None,
+            elapsed: Some(time),
+            timing: None,
+            split: None,
+            baseline: None,
+            budget: Some(budget),
+            allocations: None,
+        })
+    }
+
+    fn write(&mut self, row: Row) -> io::Result<()> {
+        match self.format {
+            Format::Text => Ok(()),
+            Format::Json => {
+                let fields: Vec<String> = COLUMNS
+                    .iter()
+                    .zip(row.values())
+                    .map(|(column, value)| {
+                        let value = match value {
+                            Some(Value::Str(s)) => json_string(&s),
+                            Some(Value::Number(n)) => n.to_string(),
+                            None => "null".to_owned(),
+                        };
+                        format!("\"{}\":{}", column, value)
+                    })
+                    .collect();
+                writeln!(self.out, "{{{}}}", fields.join(","))
+            }
+            Format::Csv => {
+                let fields: Vec<String> = row
+                    .values()
+                    .into_iter()
+                    .map(|value| match value {
+                        Some(Value::Str(s)) => csv_field(&s),
+                        Some(Value::Number(n)) => n.to_string(),
+                        None => String::new(),
+                    })
+                    .collect();
+                writeln!(self.out, "{}", fields.join(","))
+            }
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut str = String::with_capacity(value.len() + 2);
+    str.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => str.push_str("\\\""),
+            '\\' => str.push_str("\\\\"),
+            '\n' => str.push_str("\\n"),
+            '\r' => str.push_str("\\r"),
+            '\t' => str.push_str("\\t"),
+            c if c.is_control() => str.push_str(&format!("\\u{:04x}", c as u32)),
+            c => str.push(c),
+        }
+    }
+    str.push('"');
+    str
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod output_tests {
+    use super::*;
+    use shared::input::Input;
+    use std::cmp::Ordering;
+    use test_case::test_case;
+
+    const DAY: Day = Day {
+        number: 4,
+        title: "Printing \"Department\"",
+        parts: &[],
+        stages: &[],
+        input: Input::new(4, "_input.txt"),
+    };
+
+    fn render(format: Format) -> String {
+        let mut reporter = Reporter::new(format, Vec::new()).unwrap();
+        reporter
+            .solved(
+                &DAY,
+                1,
+                &Solution::Usize(1604),
+                &Verdict::Wrong("1603".to_owned()),
+                &Measurement {
+                    timing: Timing::of(&[
+                        Duration::from_nanos(1000),
+                        Duration::from_nanos(1500),
+                        Duration::from_nanos(2600),
+                    ]),
+                    split: Some(Split::new(
+                        Timing::single(Duration::from_nanos(400)),
+                        Timing::single(Duration::from_nanos(1100)),
+                    )),
+                    allocations: Some(Allocations {
+                        count: 3,
+                        bytes: 4096,
+                        peak: 2048,
+                        held: 1024,
+                    }),
+                },
+            )
+            .unwrap();
+        reporter
+            .failed(&DAY, 2, &SolveError::at(3, 7, "expected number").on_day(4))
+            .unwrap();
+        reporter
+            .compared(
+                &DAY,
+                1,
+                &Comparison {
+                    baseline: Duration::from_nanos(1000),
+                    current: Duration::from_nanos(1500),
+                    change: Ordering::Greater,
+                },
+            )
+            .unwrap();
+        reporter
+            .day(&DAY, &Timing::single(Duration::from_nanos(1500)))
+            .unwrap();
+        reporter
+            .over_budget(&DAY, Duration::from_nanos(1500), Duration::from_nanos(1200))
+            .unwrap();
+        reporter.wall(Duration::from_nanos(900)).unwrap();
+        reporter
+            .total(Duration::from_nanos(1500), Duration::from_nanos(2000))
+            .unwrap();
+        String::from_utf8(reporter.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn json_records() {
+        let expected = concat!(
+            r#"{"record":"part","day":4,"part":1,"title":"Printing \"Department\"","status":"solved","answer":"1604","answer_type":"usize","verdict":"wrong","expected":"1603","error":null,"elapsed_ns":1500,"runs":3,"min_ns":1000,"mean_ns":1700,"stddev_ns":819,"parse_ns":400,"solve_ns":1100,"baseline_ns":null,"budget_ns":null,"allocations":3,"allocated_bytes":4096,"peak_bytes":2048}"#,
+            "\n",
+            r#"{"record":"part","day":4,"part":2,"title":"Printing \"Department\"","status":"failed","answer":null,"answer_type":null,"verdict":null,"expected":null,"error":"day 4, line 3, column 7: expected number","elapsed_ns":null,"runs":null,"min_ns":null,"mean_ns":null,"stddev_ns":null,"parse_ns":null,"solve_ns":null,"baseline_ns":null,"budget_ns":null,"allocations":null,"allocated_bytes":null,"peak_bytes":null}"#,
+            "\n",
+            r#"{"record":"baseline","day":4,"part":1,"title":"Printing \"Department\"","status":"slower","answer":null,"answer_type":null,"verdict":null,"expected":null,"error":null,"elapsed_ns":1500,"runs":null,"min_ns":null,"mean_ns":null,"stddev_ns":null,"parse_ns":null,"solve_ns":null,"baseline_ns":1000,"budget_ns":null,"allocations":null,"allocated_bytes":null,"peak_bytes":null}"#,
+            "\n",
+            r#"{"record":"day","day":4,"part":null,"title":"Printing \"Department\"","status":"solved","answer":null,"answer_type":null,"verdict":null,"expected":null,"error":null,"elapsed_ns":1500,"runs":1,"min_ns":1500,"mean_ns":1500,"stddev_ns":0,"parse_ns":null,"solve_ns":null,"baseline_ns":null,"budget_ns":null,"allocations":null,"allocated_bytes":null,"peak_bytes":null}"#,
+            "\n",
+            r#"{"record":"budget","day":4,"part":null,"title":"Printing \"Department\"","status":"over","answer":null,"answer_type":null,"verdict":null,"expected":null,"error":null,"elapsed_ns":1500,"runs":null,"min_ns":null,"mean_ns":null,"stddev_ns":null,"parse_ns":null,"solve_ns":null,"baseline_ns":null,"budget_ns":1200,"allocations":null,"allocated_bytes":null,"peak_bytes":null}"#,
+            "\n",
+            r#"{"record":"wall","day":null,"part":null,"title":"Wall clock","status":"solved","answer":null,"answer_type":null,"verdict":null,"expected":null,"error":null,"elapsed_ns":900,"runs":null,"min_ns":null,"mean_ns":null,"stddev_ns":null,"parse_ns":null,"solve_ns":null,"baseline_ns":null,"budget_ns":null,"allocations":null,"allocated_bytes":null,"peak_bytes":null}"#,
+            "\n",
+            r#"{"record":"total","day":null,"part":null,"title":"Total","status":"solved","answer":null,"answer_type":null,"verdict":null,"expected":null,"error":null,"elapsed_ns":1500,"runs":null,"min_ns":null,"mean_ns":null,"stddev_ns":null,"parse_ns":null,"solve_ns":null,"baseline_ns":null,"budget_ns":2000,"allocations":null,"allocated_bytes":null,"peak_bytes":null}"#,
+            "\n",
+        );
+        assert_eq!(render(Format::Json), expected);
+    }
+
+    #[test]
+    fn csv_records() {
+        let expected = concat!(
+            "record,day,part,title,status,answer,answer_type,verdict,expected,error,elapsed_ns,runs,min_ns,mean_ns,stddev_ns,parse_ns,solve_ns,baseline_ns,budget_ns,allocations,allocated_bytes,peak_bytes\n",
+            "part,4,1,\"Printing \"\"Department\"\"\",solved,1604,usize,wrong,1603,,1500,3,1000,1700,819,400,1100,,,3,4096,2048\n",
+            "part,4,2,\"Printing \"\"Department\"\"\",failed,,,,,\"day 4, line 3, column 7: expected number\",,,,,,,,,,,,\n",
+            "baseline,4,1,\"Printing \"\"Department\"\"\",slower,,,,,,1500,,,,,,,1000,,,,\n",
+            "day,4,,\"Printing \"\"Department\"\"\",solved,,,,,,1500,1,1500,1500,0,,,,,,,\n",
+            "budget,4,,\"Printing \"\"Department\"\"\",over,,,,,,1500,,,,,,,,1200,,,\n",
+            "wall,,,Wall clock,solved,,,,,,900,,,,,,,,,,,\n",
+            "total,,,Total,solved,,,,,,1500,,,,,,,,2000,,,\n",
+        );
+        assert_eq!(render(Format::Csv), expected);
+    }
+
+    #[test]
+    fn missing_records_why() {
+        let mut reporter = Reporter::new(Format::Csv, Vec::new()).unwrap();
+        let error = InputError::Missing("inputs/04.txt".into());
+        reporter.missing(&DAY, &error).unwrap();
+        let output = String::from_utf8(reporter.into_inner()).unwrap();
+        assert_eq!(
+            output.lines().nth(1),
+            Some(
+                "day,4,,\"Printing \"\"Department\"\"\",missing,,,,,input missing at inputs/04.txt,,,,,,,,,,,,"
+            )
+        );
+    }
+
+    #[test_case("a\nb", r#""a\nb""#)]
+    #[test_case("tab\there", r#""tab\there""#)]
+    #[test_case("back\\slash", r#""back\\slash""#)]
+    fn json_escaping(value: &str, expected: &str) {
+        assert_eq!(json_string(value), expected);
+    }
+}
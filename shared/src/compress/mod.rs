@@ -0,0 +1,349 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+    grid::Grid,
+    points::{directions::CARDINALS, iline::ILine, ipoint::IPoint, point::Point},
+};
+
+/// One axis of a compressed grid: every distinct coordinate is a cell of its own, every gap
+/// between two of them is one more cell, and one cell past either end keeps the outside connected.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Axis {
+    starts: Vec<isize>,
+}
+
+impl Axis {
+    pub fn new(coordinates: impl IntoIterator<Item = isize>) -> Self {
+        let mut coordinates: Vec<_> = coordinates.into_iter().collect();
+        coordinates.sort_unstable();
+        coordinates.dedup();
+
+        let (Some(&first), Some(&last)) = (coordinates.first(), coordinates.last()) else {
+            return Self { starts: Vec::new() };
+        };
+
+        let mut starts = Vec::with_capacity(coordinates.len() * 2 + 2);
+        starts.push(first - 1);
+        for pair in coordinates.windows(2) {
+            starts.push(pair[0]);
+            if pair[1] > pair[0] + 1 {
+                starts.push(pair[0] + 1);
+            }
+        }
+        starts.push(last);
+        starts.push(last + 1);
+        Self { starts }
+    }
+
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// The cell holding `coordinate`, if it is within the outermost cells.
+    pub fn cell(&self, coordinate: isize) -> Option<usize> {
+        let last = *self.starts.last()?;
+        if coordinate < self.starts[0] || coordinate > last {
+            return None;
+        }
+        Some(self.starts.partition_point(|&start| start <= coordinate) - 1)
+    }
+
+    /// The original coordinates `cell` stands for.
+    pub fn range(&self, cell: usize) -> RangeInclusive<isize> {
+        let start = self.starts[cell];
+        let end = self.starts.get(cell + 1).map_or(start, |next| next - 1);
+        start..=end
+    }
+
+    pub fn weight(&self, cell: usize) -> usize {
+        let range = self.range(cell);
+        range.start().abs_diff(*range.end()) + 1
+    }
+}
+
+/// Maps points with far apart coordinates onto a small grid whose cells each stand for a
+/// rectangle of the original points, all of which behave the same for lines along the input.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Compressed {
+    pub x: Axis,
+    pub y: Axis,
+}
+
+impl Compressed {
+    pub fn new(points: impl IntoIterator<Item = IPoint>) -> Self {
+        let (xs, ys): (Vec<_>, Vec<_>) = points.into_iter().map(|point| (point.x, point.y)).unzip();
+        Self {
+            x: Axis::new(xs),
+            y: Axis::new(ys),
+        }
+    }
+
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a ILine>) -> Self {
+        Self::new(lines.into_iter().flat_map(|line| [line.min, line.max]))
+    }
+
+    pub fn width(&self) -> usize {
+        self.x.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.y.len()
+    }
+
+    pub fn grid<T: Clone>(&self, fill: T) -> Grid<T> {
+        Grid::filled(fill, self.height(), self.width())
+    }
+
+    pub fn point(&self, original: IPoint) -> Option<Point> {
+        Some(Point::new(
+            self.x.cell(original.x)?,
+            self.y.cell(original.y)?,
+        ))
+    }
+
+    /// The smallest and largest original point `point` stands for, both inclusive.
+    pub fn original(&self, point: Point) -> (IPoint, IPoint) {
+        let (xs, ys) = (self.x.range(point.x), self.y.range(point.y));
+        (
+            IPoint::new(*xs.start(), *ys.start()),
+            IPoint::new(*xs.end(), *ys.end()),
+        )
+    }
+
+    /// How many original points `point` stands for.
+    pub fn weight(&self, point: Point) -> usize {
+        self.x.weight(point.x) * self.y.weight(point.y)
+    }
+
+    pub fn weights(&self) -> Grid<usize> {
+        let mut weights = self.grid(0);
+        for point in weights.points() {
+            weights[point] = self.weight(point);
+        }
+        weights
+    }
+
+    /// Sets every cell `line` passes through. Lines are expected to be horizontal or vertical,
+    /// anything else sets its whole bounding box.
+    pub fn draw<T: Clone>(&self, grid: &mut Grid<T>, line: &ILine, value: T) {
+        let (Some(from), Some(to)) = (self.point(line.min), self.point(line.max)) else {
+            return;
+        };
+
+        for y in from.y.min(to.y)..=from.y.max(to.y) {
+            for x in from.x.min(to.x)..=from.x.max(to.x) {
+                grid[(x, y)] = value.clone();
+            }
+        }
+    }
+
+    /// The cells on or inside the closed polygon through `corners`.
+    pub fn polygon(&self, corners: &[IPoint]) -> Grid<bool> {
+        let mut edge = self.grid(false);
+        for (i, &corner) in corners.iter().enumerate() {
+            let next = corners[(i + 1) % corners.len()];
+            self.draw(&mut edge, &ILine::new(corner, next), true);
+        }
+
+        if edge.vec.is_empty() {
+            return edge;
+        }
+
+        let mut inside = self.grid(true);
+        let mut stack = vec![Point::new(0, 0)];
+        inside[Point::new(0, 0)] = false;
+        while let Some(point) = stack.pop() {
+            for (_, next) in edge.neighbours(point, CARDINALS) {
+                if inside[next] && !edge[next] {
+                    inside[next] = false;
+                    stack.push(next);
+                }
+            }
+        }
+        inside
+    }
+
+    /// How many original points the set cells stand for.
+    pub fn area(&self, grid: &Grid<bool>) -> usize {
+        grid.points()
+            .filter(|&point| grid[point])
+            .map(|point| self.weight(point))
+            .sum()
+    }
+
+    /// Whether every cell of the rectangle with corners `a` and `b` is set.
+    pub fn contains(&self, set: &SummedArea, a: IPoint, b: IPoint) -> bool {
+        let (Some(a), Some(b)) = (self.point(a), self.point(b)) else {
+            return false;
+        };
+
+        let (from, to) = (
+            Point::new(a.x.min(b.x), a.y.min(b.y)),
+            Point::new(a.x.max(b.x), a.y.max(b.y)),
+        );
+        set.count(from, to) == (to.x - from.x + 1) * (to.y - from.y + 1)
+    }
+}
+
+/// How many cells are set above and left of every corner, to count any rectangle in constant time.
+#[derive(Clone, Debug)]
+pub struct SummedArea {
+    sums: Grid<usize>,
+}
+
+impl SummedArea {
+    pub fn new(grid: &Grid<bool>) -> Self {
+        let mut sums = Grid::filled(0, grid.height + 1, grid.width + 1);
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                sums[(x + 1, y + 1)] =
+                    grid[(x, y)] as usize + sums[(x, y + 1)] + sums[(x + 1, y)] - sums[(x, y)];
+            }
+        }
+        Self { sums }
+    }
+
+    /// The set cells from `from` to `to`, both inclusive.
+    pub fn count(&self, from: Point, to: Point) -> usize {
+        let (x1, y1, x2, y2) = (from.x, from.y, to.x + 1, to.y + 1);
+        self.sums[(x2, y2)] + self.sums[(x1, y1)] - self.sums[(x1, y2)] - self.sums[(x2, y1)]
+    }
+}
+
+#[cfg(test)]
+mod compress_tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn axis() -> Axis {
+        Axis::new([10, 3, 4, 10, 100])
+    }
+
+    #[test]
+    fn axis_cells_cover_every_coordinate_once() {
+        let axis = axis();
+        let ranges: Vec<_> = (0..axis.len()).map(|cell| axis.range(cell)).collect();
+        assert_eq!(
+            ranges,
+            [
+                2..=2,
+                3..=3,
+                4..=4,
+                5..=9,
+                10..=10,
+                11..=99,
+                100..=100,
+                101..=101
+            ]
+        );
+    }
+
+    #[test_case(1, None)]
+    #[test_case(2, Some(0))]
+    #[test_case(4, Some(2))]
+    #[test_case(7, Some(3))]
+    #[test_case(99, Some(5))]
+    #[test_case(101, Some(7))]
+    #[test_case(102, None)]
+    fn axis_cell(coordinate: isize, expected: Option<usize>) {
+        assert_eq!(axis().cell(coordinate), expected);
+    }
+
+    #[test]
+    fn axis_weights_add_up_to_the_span() {
+        let axis = axis();
+        let total: usize = (0..axis.len()).map(|cell| axis.weight(cell)).sum();
+        assert_eq!(total, 100);
+    }
+
+    #[test]
+    fn empty_axis() {
+        let axis = Axis::new([]);
+        assert!(axis.is_empty());
+        assert_eq!(axis.cell(0), None);
+    }
+
+    #[test]
+    fn original_undoes_point() {
+        let compressed = Compressed::new([IPoint::new(-50, 7), IPoint::new(1000, -3)]);
+        for point in compressed.grid(()).points() {
+            let (min, max) = compressed.original(point);
+            assert_eq!(compressed.point(min), Some(point));
+            assert_eq!(compressed.point(max), Some(point));
+            assert_eq!(
+                compressed.weight(point),
+                (max.x - min.x + 1) as usize * (max.y - min.y + 1) as usize
+            );
+        }
+    }
+
+    fn corners(corners: &[(isize, isize)]) -> Vec<IPoint> {
+        corners.iter().map(|&(x, y)| IPoint::new(x, y)).collect()
+    }
+
+    #[test_case(&[(0, 0), (9, 0), (9, 4), (0, 4)], 50)]
+    #[test_case(&[(0, 0), (1000, 0), (1000, 1000), (0, 1000)], 1001 * 1001)]
+    #[test_case(&[(0, 0), (10, 0), (10, 10), (6, 10), (6, 4), (4, 4), (4, 10), (0, 10)], 121 - 6)]
+    #[test_case(&[(7, 1), (11, 1), (11, 7), (9, 7), (9, 5), (2, 5), (2, 3), (7, 3)], 46)]
+    fn polygon_area(corners_: &[(isize, isize)], expected: usize) {
+        let corners = corners(corners_);
+        let compressed = Compressed::new(corners.iter().copied());
+        assert_eq!(compressed.area(&compressed.polygon(&corners)), expected);
+    }
+
+    #[test]
+    fn contains_rectangles_inside_the_polygon() {
+        let corners = corners(&[
+            (0, 0),
+            (10, 0),
+            (10, 10),
+            (6, 10),
+            (6, 4),
+            (4, 4),
+            (4, 10),
+            (0, 10),
+        ]);
+        let compressed = Compressed::new(corners.iter().copied());
+        let tiles = SummedArea::new(&compressed.polygon(&corners));
+
+        assert!(compressed.contains(&tiles, IPoint::new(0, 0), IPoint::new(10, 4)));
+        assert!(compressed.contains(&tiles, IPoint::new(4, 10), IPoint::new(0, 0)));
+        assert!(!compressed.contains(&tiles, IPoint::new(0, 0), IPoint::new(10, 10)));
+        assert!(!compressed.contains(&tiles, IPoint::new(0, 0), IPoint::new(11, 4)));
+    }
+
+    #[test]
+    fn summed_area_counts_every_rectangle() {
+        let grid = Grid::from(
+            [1, 0, 1, 1, 1, 1, 0, 0, 1, 1, 0, 1]
+                .map(|cell| cell == 1)
+                .to_vec(),
+            3,
+        );
+        let summed = SummedArea::new(&grid);
+        for from in grid.points() {
+            for to in grid.points().filter(|to| to.x >= from.x && to.y >= from.y) {
+                let expected = (from.y..=to.y)
+                    .flat_map(|y| (from.x..=to.x).map(move |x| (x, y)))
+                    .filter(|&point| grid[point])
+                    .count();
+                assert_eq!(summed.count(from, to), expected, "{} to {}", from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn weights_match_weight() {
+        let compressed = Compressed::from_lines(&[
+            ILine::new(IPoint::new(0, 0), IPoint::new(0, 20)),
+            ILine::new(IPoint::new(5, 3), IPoint::new(9, 3)),
+        ]);
+        let weights = compressed.weights();
+        assert_eq!((weights.width, weights.height), (7, 7));
+        assert_eq!(weights.vec.iter().sum::<usize>(), 12 * 23);
+    }
+}
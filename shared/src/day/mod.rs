@@ -0,0 +1,160 @@
+use std::any::Any;
+
+use crate::{Solution, error::SolveError, input::Input};
+
+pub type Part = fn(&str) -> Result<Solution, SolveError>;
+pub type Parsed = Box<dyn Any>;
+pub type Parse = fn(&str) -> Result<Parsed, SolveError>;
+pub type Solve = fn(&dyn Any) -> Result<Solution, SolveError>;
+
+pub struct Stage {
+    pub parse: Parse,
+    pub solve: &'static [Solve],
+}
+
+pub struct Day {
+    pub number: u32,
+    pub title: &'static str,
+    pub parts: &'static [Part],
+    pub stages: &'static [Stage],
+    pub input: Input,
+}
+
+inventory::collect!(Day);
+
+#[macro_export]
+macro_rules! day {
+    (
+        $number:literal,
+        $title:literal,
+        [$($part:path),* $(,)?]
+        $(, $parse:path => [$($solve:path),* $(,)?])* $(,)?
+    ) => {
+        pub const DAY: $crate::day::Day = $crate::day::Day {
+            number: $number,
+            title: $title,
+            parts: &[$($part),*],
+            stages: &[$($crate::day::Stage {
+                parse: |input| $crate::day::erase($parse(input)),
+                solve: &[$(|parsed| $crate::day::solved($solve(::std::borrow::Borrow::borrow($crate::day::downcast(parsed, $parse))))),*],
+            }),*],
+            input: $crate::input!($number),
+        };
+
+        $crate::inventory::submit! { DAY }
+    };
+}
+
+impl Day {
+    pub fn id(&self, part: usize) -> String {
+        format!("{:02}.{}", self.number, part)
+    }
+
+    pub fn parts(&self) -> impl Iterator<Item = (usize, &Part)> {
+        self.parts.iter().enumerate().map(|(i, part)| (i + 1, part))
+    }
+
+    pub fn phases(&self) -> impl Iterator<Item = (usize, Parse, Solve)> {
+        self.stages
+            .iter()
+            .flat_map(|stage| stage.solve.iter().map(|solve| (stage.parse, *solve)))
+            .enumerate()
+            .map(|(i, (parse, solve))| (i + 1, parse, solve))
+    }
+
+    pub fn phase(&self, part: usize) -> Option<(Parse, Solve)> {
+        self.phases()
+            .find(|(number, _, _)| *number == part)
+            .map(|(_, parse, solve)| (parse, solve))
+    }
+
+    pub fn run(&self, input: &str) -> Vec<Result<Solution, SolveError>> {
+        let mut results = Vec::with_capacity(self.parts.len());
+        for stage in self.stages {
+            match (stage.parse)(input) {
+                Ok(parsed) => {
+                    results.extend(stage.solve.iter().map(|solve| solve(parsed.as_ref())))
+                }
+                Err(error) => results.extend(stage.solve.iter().map(|_| Err(error.clone()))),
+            }
+        }
+        results
+    }
+}
+
+pub fn erase<T: Any>(parsed: Result<T, SolveError>) -> Result<Parsed, SolveError> {
+    parsed.map(|parsed| Box::new(parsed) as Parsed)
+}
+
+pub fn downcast<T: Any>(parsed: &dyn Any, _parse: fn(&str) -> Result<T, SolveError>) -> &T {
+    parsed
+        .downcast_ref()
+        .expect("parsed value does not match the parse function of its stage")
+}
+
+pub fn solved<S: Into<Solution>>(result: Result<S, SolveError>) -> Result<Solution, SolveError> {
+    result.map(Into::into)
+}
+
+pub fn days() -> Vec<&'static Day> {
+    let mut days: Vec<&'static Day> = inventory::iter::<Day>.into_iter().collect();
+    days.sort_by_key(|day| day.number);
+    days
+}
+
+#[cfg(test)]
+mod day_tests {
+    use crate::{Solution, error::SolveError};
+
+    fn parse(input: &str) -> Result<Vec<u32>, SolveError> {
+        input
+            .split(',')
+            .map(|n| n.parse().map_err(|_| SolveError::new("expected number")))
+            .collect()
+    }
+
+    fn sum(numbers: &[u32]) -> Result<u32, SolveError> {
+        Ok(numbers.iter().sum())
+    }
+
+    fn max(numbers: &[u32]) -> Result<Option<u32>, SolveError> {
+        Ok(numbers.iter().max().copied())
+    }
+
+    fn length(input: &str) -> Result<usize, SolveError> {
+        Ok(input.len())
+    }
+
+    fn count(input: &usize) -> Result<usize, SolveError> {
+        Ok(*input)
+    }
+
+    fn part(_input: &str) -> Result<Solution, SolveError> {
+        Ok(Solution::None)
+    }
+
+    crate::day!(99, "Test", [part, part, part], parse => [sum, max], length => [count]);
+
+    #[test]
+    fn phases_are_numbered_across_stages() {
+        let parts: Vec<usize> = DAY.phases().map(|(part, _, _)| part).collect();
+        assert_eq!(parts, vec![1, 2, 3]);
+        assert!(DAY.phase(4).is_none());
+    }
+
+    #[test]
+    fn run_shares_parse_between_parts() {
+        assert_eq!(
+            DAY.run("1,5,3"),
+            vec![Ok(9u32.into()), Ok(5u32.into()), Ok(5usize.into())]
+        );
+    }
+
+    #[test]
+    fn run_reports_parse_errors_for_every_part_of_a_stage() {
+        let results = DAY.run("1,x");
+        assert_eq!(results[0], Err(SolveError::new("expected number")));
+        assert_eq!(results[1], Err(SolveError::new("expected number")));
+        assert_eq!(results[2], Ok(3usize.into()));
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    fmt,
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::{Solution, day::Part, generate::Generator};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Mismatch {
+    pub seed: u64,
+    pub input: String,
+    pub fast: Result<Solution, String>,
+    pub reference: Result<Solution, String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |result: &Result<Solution, String>| match result {
+            Ok(solution) => solution.to_string(),
+            Err(error) => format!("error: {}", error),
+        };
+
+        writeln!(
+            f,
+            "seed {}: fast gave {}, reference gave {} on",
+            self.seed,
+            show(&self.fast),
+            show(&self.reference)
+        )?;
+        write!(f, "{}", self.input)
+    }
+}
+
+/// Runs `fast` and `reference` on an input generated from each seed and returns the first
+/// input they disagree on, shrunk to as few lines (or comma separated items) as still disagree.
+///
+/// While shrinking, inputs the reference rejects are skipped.
+pub fn differential(
+    fast: Part,
+    reference: Part,
+    generate: Generator,
+    size: usize,
+    seeds: Range<u64>,
+) -> Result<(), Mismatch> {
+    let results = |input: &str| (run(fast, input), run(reference, input));
+
+    for seed in seeds {
+        let mut input = generate(size, seed);
+        let (actual, expected) = results(&input);
+        if actual == expected {
+            continue;
+        }
+
+        if expected.is_ok() {
+            input = minimize(&input, |input| {
+                let (actual, expected) = results(input);
+                expected.is_ok() && actual != expected
+            });
+        }
+
+        let (fast, reference) = results(&input);
+        return Err(Mismatch {
+            seed,
+            input,
+            fast,
+            reference,
+        });
+    }
+
+    Ok(())
+}
+
+/// Panics with the first mismatch [`differential`] finds, for use in tests.
+pub fn assert_matches(
+    fast: Part,
+    reference: Part,
+    generate: Generator,
+    size: usize,
+    seeds: Range<u64>,
+) {
+    if let Err(mismatch) = differential(fast, reference, generate, size, seeds) {
+        panic!("{}", mismatch);
+    }
+}
+
+fn run(part: Part, input: &str) -> Result<Solution, String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| part(input))) {
+        Ok(Ok(solution)) => Ok(solution),
+        Ok(Err(error)) => Err(error.to_string()),
+        Err(_) => Err("panicked".to_owned()),
+    }
+}
+
+/// Removes ever smaller chunks of lines, or of comma separated items for single line inputs,
+/// for as long as `fails` keeps holding.
+pub fn minimize(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let (separator, trailing) = if input.trim_end().contains('\n') {
+        ("\n", input.ends_with('\n'))
+    } else {
+        (",", false)
+    };
+    let join = |units: &[&str]| {
+        let mut input = units.join(separator);
+        if trailing {
+            input.push('\n');
+        }
+        input
+    };
+
+    let mut units: Vec<&str> = input.trim_end_matches('\n').split(separator).collect();
+    let mut chunk = units.len().div_ceil(2);
+
+    while chunk > 0 {
+        let mut start = 0;
+        let mut removed = false;
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let candidate: Vec<&str> = [&units[..start], &units[end..]].concat();
+            if !candidate.is_empty() && fails(&join(&candidate)) {
+                units = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+
+        if !removed {
+            chunk /= 2;
+        }
+    }
+
+    join(&units)
+}
+
+#[cfg(test)]
+mod differential_tests {
+    use super::*;
+    use crate::error::SolveError;
+    use test_case::test_case;
+
+    fn lines(input: &str) -> Result<Solution, SolveError> {
+        Ok(input.lines().count().into())
+    }
+
+    fn lines_but_sevens(input: &str) -> Result<Solution, SolveError> {
+        Ok(input
+            .lines()
+            .filter(|line| !line.contains('7'))
+            .count()
+            .into())
+    }
+
+    fn numbers(size: usize, seed: u64) -> String {
+        (0..size as u64)
+            .map(|i| format!("{}\n", i * seed))
+            .collect()
+    }
+
+    #[test]
+    fn agreeing_parts() {
+        assert_eq!(differential(lines, lines, numbers, 20, 0..10), Ok(()));
+    }
+
+    #[test]
+    fn first_mismatch_is_minimized() {
+        let mismatch = differential(lines_but_sevens, lines, numbers, 20, 0..10).unwrap_err();
+        assert_eq!(mismatch.seed, 1);
+        assert_eq!(mismatch.input, "17\n");
+        assert_eq!(mismatch.fast, Ok(Solution::Usize(0)));
+        assert_eq!(mismatch.reference, Ok(Solution::Usize(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "seed 1: fast gave 0, reference gave 1 on\n17\n")]
+    fn assert_matches_panics_with_the_mismatch() {
+        assert_matches(lines_but_sevens, lines, numbers, 20, 0..10);
+    }
+
+    #[test_case("1\n2\n3\n4\n5\n6\n", "4\n")]
+    #[test_case("1,2,3,4,5,6", "4")]
+    #[test_case("1\n2\n3\n4\n5\n6", "4")]
+    fn minimize_keeps_failing_unit(input: &str, expected: &str) {
+        assert_eq!(minimize(input, |input| input.contains('4')), expected);
+    }
+
+    #[test]
+    fn minimize_keeps_units_that_fail_together() {
+        let fails = |input: &str| input.contains("2\n") && input.contains("5\n");
+        assert_eq!(minimize("1\n2\n3\n4\n5\n6\n", fails), "2\n5\n");
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    pub fn of(input: &str, offset: usize) -> Self {
+        let before = &input.as_bytes()[..offset.min(input.len())];
+        let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+        let column = match before.iter().rposition(|&b| b == b'\n') {
+            Some(newline) => offset - newline,
+            None => offset + 1,
+        };
+
+        Self { line, column }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SolveError {
+    pub day: Option<u32>,
+    pub position: Option<Position>,
+    pub reason: String,
+}
+
+impl SolveError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            position: None,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn at(line: usize, column: usize, reason: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            position: Some(Position::new(line, column)),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn at_offset(input: &str, offset: usize, reason: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            position: Some(Position::of(input, offset)),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn on_day(mut self, day: u32) -> Self {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        if let Some(position) = self.position {
+            write!(f, "{}: ", position)?;
+        }
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("abc", 0, 1, 1)]
+    #[test_case("abc", 2, 1, 3)]
+    #[test_case("ab\ncd", 3, 2, 1)]
+    #[test_case("ab\ncd\nef", 7, 3, 2)]
+    #[test_case("ab\n", 3, 2, 1)]
+    fn position_of_offset(input: &str, offset: usize, line: usize, column: usize) {
+        assert_eq!(Position::of(input, offset), Position::new(line, column));
+    }
+
+    #[test]
+    fn display() {
+        let error = SolveError::at(3, 7, "expected number").on_day(5);
+        assert_eq!(
+            error.to_string(),
+            "day 5, line 3, column 7: expected number"
+        );
+        assert_eq!(SolveError::new("empty input").to_string(), "empty input");
+    }
+}
@@ -0,0 +1,30 @@
+use super::Rng;
+
+/// Day 3: `size` banks of 12 to 16 batteries, each rated 1 to 9.
+pub fn banks(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let length = rng.range(12, 16);
+        input.extend((0..length).map(|_| (b'0' + rng.range(1, 9) as u8) as char));
+        input.push('\n');
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod banks_tests {
+    use super::*;
+
+    #[test]
+    fn every_bank_has_enough_batteries() {
+        let input = banks(50, 2);
+        assert_eq!(input.lines().count(), 50);
+        for line in input.lines() {
+            assert!((12..=16).contains(&line.len()));
+            assert!(line.bytes().all(|b| (b'1'..=b'9').contains(&b)));
+        }
+    }
+}
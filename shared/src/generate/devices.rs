@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use super::Rng;
+
+const RESERVED: [&str; 5] = ["you", "svr", "dac", "fft", "out"];
+const MAX_PATHS: u64 = 1 << 48;
+
+/// Day 11: a DAG of `size` devices plus `svr`, `you`, `dac` and `fft`, all leading to `out`.
+///
+/// Devices only connect to devices further along, and edges are dropped where they would
+/// push the number of paths past 2^48.
+pub fn devices(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.min(26 * 26 * 26 - RESERVED.len());
+
+    let mut used: HashSet<String> = RESERVED.iter().map(|name| name.to_string()).collect();
+    let mut names: Vec<String> = (0..size)
+        .map(|_| {
+            loop {
+                let name: String = (0..3)
+                    .map(|_| (b'a' + rng.below(26) as u8) as char)
+                    .collect();
+                if used.insert(name.clone()) {
+                    break name;
+                }
+            }
+        })
+        .collect();
+
+    let middle = |rng: &mut Rng, names: &[String]| 1 + rng.below(names.len());
+    names.insert(0, "svr".to_owned());
+    let you = 1 + rng.below(names.len().div_ceil(2));
+    names.insert(you, "you".to_owned());
+    let dac = middle(&mut rng, &names);
+    names.insert(dac, "dac".to_owned());
+    let fft = middle(&mut rng, &names);
+    names.insert(fft, "fft".to_owned());
+    names.push("out".to_owned());
+
+    let out = names.len() - 1;
+    let mut paths = vec![0u64; names.len()];
+    paths[out] = 1;
+    let mut outputs = vec![Vec::new(); out];
+
+    for i in (0..out).rev() {
+        let window = (out - i).min(8);
+        let mut targets: Vec<usize> = (0..rng.range(1, 3))
+            .map(|_| i + 1 + rng.below(window))
+            .collect();
+        targets.sort();
+        targets.dedup();
+
+        for target in targets {
+            if outputs[i].is_empty() || paths[i] + paths[target] <= MAX_PATHS {
+                paths[i] += paths[target];
+                outputs[i].push(target);
+            }
+        }
+    }
+
+    let mut lines: Vec<String> = outputs
+        .iter()
+        .enumerate()
+        .map(|(i, outputs)| {
+            let outputs: Vec<_> = outputs.iter().map(|&o| names[o].as_str()).collect();
+            format!("{}: {}\n", names[i], outputs.join(" "))
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+#[cfg(test)]
+mod devices_tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0, 1)]
+    #[test_case(10, 2)]
+    #[test_case(500, 3)]
+    fn every_device_but_out_is_listed_once(size: usize, seed: u64) {
+        let input = devices(size, seed);
+        let listed: Vec<&str> = input
+            .lines()
+            .map(|line| line.split_once(':').unwrap().0)
+            .collect();
+        let unique: HashSet<&str> = listed.iter().copied().collect();
+
+        assert_eq!(listed.len(), size + 4);
+        assert_eq!(unique.len(), listed.len());
+        assert!(!unique.contains("out"));
+        for line in input.lines() {
+            let (_, outputs) = line.split_once(": ").unwrap();
+            assert!(
+                outputs
+                    .split(' ')
+                    .all(|output| output == "out" || unique.contains(output))
+            );
+        }
+    }
+}
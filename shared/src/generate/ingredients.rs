@@ -0,0 +1,36 @@
+use super::Rng;
+
+/// Day 5: `size` short, often overlapping ranges of fresh IDs, a blank line and `size` IDs,
+/// all below 150.
+pub fn ingredients(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size.max(1) {
+        let start = rng.range(1, 120);
+        input.push_str(&format!("{}-{}\n", start, start + rng.range(0, 20)));
+    }
+    input.push('\n');
+    for _ in 0..size {
+        input.push_str(&format!("{}\n", rng.range(1, 149)));
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod ingredients_tests {
+    use super::*;
+
+    #[test]
+    fn ranges_then_ids() {
+        let input = ingredients(30, 5);
+        let (ranges, ids) = input.split_once("\n\n").unwrap();
+        assert_eq!(ranges.lines().count(), 30);
+        assert_eq!(ids.lines().count(), 30);
+        for range in ranges.lines() {
+            let (start, end) = range.split_once('-').unwrap();
+            assert!(start.parse::<u64>().unwrap() <= end.parse().unwrap());
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use super::Rng;
+
+/// Day 8: `size` junction boxes below 100000 on every axis.
+///
+/// No two pairs of boxes are the same distance apart, so which pairs are closest never depends
+/// on how ties are broken.
+pub fn junctions(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut boxes: Vec<[u64; 3]> = Vec::with_capacity(size);
+    let mut distances = HashSet::new();
+
+    while boxes.len() < size {
+        let candidate = [(); 3].map(|_| rng.range(0, 99_999));
+        let new: Vec<u64> = boxes
+            .iter()
+            .map(|other| (0..3).map(|i| candidate[i].abs_diff(other[i]).pow(2)).sum())
+            .collect();
+        let unique: HashSet<u64> = new.iter().copied().collect();
+        if unique.len() == new.len() && unique.is_disjoint(&distances) {
+            distances.extend(unique);
+            boxes.push(candidate);
+        }
+    }
+
+    boxes
+        .iter()
+        .map(|[x, y, z]| format!("{},{},{}\n", x, y, z))
+        .collect()
+}
+
+#[cfg(test)]
+mod junctions_tests {
+    use super::*;
+
+    #[test]
+    fn distances_are_distinct() {
+        let boxes: Vec<Vec<u64>> = junctions(60, 8)
+            .lines()
+            .map(|line| line.split(',').map(|n| n.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(boxes.len(), 60);
+
+        let mut distances = HashSet::new();
+        for (i, a) in boxes.iter().enumerate() {
+            for b in &boxes[i + 1..] {
+                let distance: u64 = a.iter().zip(b).map(|(a, b)| a.abs_diff(*b).pow(2)).sum();
+                assert!(distances.insert(distance));
+            }
+        }
+    }
+}
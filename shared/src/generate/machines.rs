@@ -0,0 +1,95 @@
+use super::Rng;
+
+/// Day 10: `size` machines whose lights and joltages are reached by pressing some buttons.
+///
+/// Lights are indexed by a single digit, and every joltage fits in ten bits.
+pub fn machines(size: usize, seed: u64) -> String {
+    build(size, seed, 10, 20)
+}
+
+/// Day 10: like [`machines`], but with at most four lights and three presses of each button,
+/// few enough to try every number of presses.
+pub fn small_machines(size: usize, seed: u64) -> String {
+    build(size, seed, 4, 3)
+}
+
+fn build(size: usize, seed: u64, most_lights: u64, most_presses: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let lights = rng.range(3, most_lights) as usize;
+        let count = rng.range(lights as u64 - 2, lights as u64 + 3).clamp(2, 13);
+        let mut buttons: Vec<Vec<usize>> = (0..count)
+            .map(|_| {
+                let mut button: Vec<usize> = (0..lights).collect();
+                rng.shuffle(&mut button);
+                button.truncate(rng.range(1, lights as u64) as usize);
+                button
+            })
+            .collect();
+        for light in 0..lights {
+            if !buttons.iter().any(|button| button.contains(&light)) {
+                let i = rng.below(buttons.len());
+                buttons[i].push(light);
+            }
+        }
+        buttons.iter_mut().for_each(|button| button.sort());
+        buttons.sort();
+        buttons.dedup();
+        rng.shuffle(&mut buttons);
+
+        let mut on = vec![false; lights];
+        let mut joltages = vec![0; lights];
+        for button in &buttons {
+            let flip = rng.chance(1, 2);
+            let presses = rng.range(0, most_presses);
+            for &light in button {
+                on[light] ^= flip;
+                joltages[light] += presses;
+            }
+        }
+
+        input.push('[');
+        input.extend(on.iter().map(|&on| if on { '#' } else { '.' }));
+        input.push(']');
+        for button in &buttons {
+            let lights: Vec<_> = button.iter().map(|light| light.to_string()).collect();
+            input.push_str(&format!(" ({})", lights.join(",")));
+        }
+        let joltages: Vec<_> = joltages.iter().map(|joltage| joltage.to_string()).collect();
+        input.push_str(&format!(" {{{}}}\n", joltages.join(",")));
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod machines_tests {
+    use super::*;
+
+    #[test]
+    fn every_machine_has_lights_buttons_and_joltages() {
+        let input = machines(30, 5);
+        assert_eq!(input.lines().count(), 30);
+        for line in input.lines() {
+            let lights = line.find(']').unwrap() - 1;
+            let joltages = &line[line.find('{').unwrap() + 1..line.len() - 1];
+            assert_eq!(joltages.split(',').count(), lights);
+            assert!(
+                joltages
+                    .split(',')
+                    .all(|j| j.parse::<u32>().unwrap() < 1024)
+            );
+            assert!(line.matches('(').count() >= 2);
+        }
+    }
+
+    #[test]
+    fn small_machines_are_small() {
+        for line in small_machines(30, 5).lines() {
+            assert!(line.find(']').unwrap() - 1 <= 4);
+            assert!(line.matches('(').count() <= 7);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use super::Rng;
+
+/// Day 7: a manifold with `size` rows of splitters below the start, each row on every other
+/// line.
+///
+/// Like in the puzzle, the splitters fan out from below the start, two columns apart, so no two
+/// of them are side by side and none is on the edge.
+pub fn manifold(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let width = 2 * size + 3;
+    let start = size + 1;
+    let line = |symbol: char, at: &[usize]| -> String {
+        (0..width)
+            .map(|x| if at.contains(&x) { symbol } else { '.' })
+            .chain(['\n'])
+            .collect()
+    };
+
+    let mut input = line('S', &[start]);
+    input.push_str(&line('^', &[]));
+    for row in 0..size {
+        let splitters: Vec<usize> = (0..=row)
+            .map(|i| start - row + 2 * i)
+            .filter(|_| rng.chance(2, 3))
+            .collect();
+        input.push_str(&line('^', &splitters));
+        input.push_str(&line('^', &[]));
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod manifold_tests {
+    use super::*;
+
+    #[test]
+    fn splitters_are_apart_and_off_the_edges() {
+        let input = manifold(10, 7);
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 22);
+        assert_eq!(lines[0].find('S'), Some(11));
+        for line in &lines {
+            assert_eq!(line.len(), 23);
+            assert!(!line.contains("^^"));
+            assert!(!line.starts_with('^') && !line.ends_with('^'));
+        }
+    }
+}
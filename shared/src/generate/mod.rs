@@ -0,0 +1,146 @@
+mod banks;
+mod devices;
+mod ingredients;
+mod junctions;
+mod machines;
+mod manifold;
+mod polygon;
+mod ranges;
+mod regions;
+mod rolls;
+mod rotations;
+mod worksheet;
+
+pub use banks::banks;
+pub use devices::devices;
+pub use ingredients::ingredients;
+pub use junctions::junctions;
+pub use machines::{machines, small_machines};
+pub use manifold::manifold;
+pub use polygon::polygon;
+pub use ranges::ranges;
+pub use regions::regions;
+pub use rolls::rolls;
+pub use rotations::rotations;
+pub use worksheet::worksheet;
+
+pub type Generator = fn(size: usize, seed: u64) -> String;
+
+pub fn for_day(day: u32) -> Option<Generator> {
+    match day {
+        1 => Some(rotations),
+        2 => Some(ranges),
+        3 => Some(banks),
+        4 => Some(rolls),
+        5 => Some(ingredients),
+        6 => Some(worksheet),
+        7 => Some(manifold),
+        8 => Some(junctions),
+        9 => Some(polygon),
+        10 => Some(machines),
+        11 => Some(devices),
+        12 => Some(regions),
+        _ => None,
+    }
+}
+
+/// SplitMix64, so that a seed always produces the same input on every platform.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `from..=to`.
+    pub fn range(&mut self, from: u64, to: u64) -> u64 {
+        assert!(from <= to, "empty range {}..={}", from, to);
+        match (to - from).checked_add(1) {
+            Some(span) => from + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    pub fn below(&mut self, to: usize) -> usize {
+        self.range(0, to as u64 - 1) as usize
+    }
+
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.range(1, denominator) <= numerator
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// `count` distinct values in strictly increasing order, each at least `gap` above the last.
+    pub fn increasing(&mut self, count: usize, gap: u64, spread: u64) -> Vec<u64> {
+        let mut value = 0;
+        (0..count)
+            .map(|_| {
+                value += self.range(gap, gap + spread);
+                value
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod generate_tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let mut c = Rng::new(8);
+        let a: Vec<_> = (0..4).map(|_| a.next_u64()).collect();
+        let b: Vec<_> = (0..4).map(|_| b.next_u64()).collect();
+        let c: Vec<_> = (0..4).map(|_| c.next_u64()).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test_case(3, 3)]
+    #[test_case(0, 9)]
+    #[test_case(0, u64::MAX)]
+    fn rng_range(from: u64, to: u64) {
+        let mut rng = Rng::new(1);
+        for _ in 0..100 {
+            let value = rng.range(from, to);
+            assert!(from <= value && value <= to);
+        }
+    }
+
+    #[test_case(rotations)]
+    #[test_case(ranges)]
+    #[test_case(banks)]
+    #[test_case(rolls)]
+    #[test_case(ingredients)]
+    #[test_case(worksheet)]
+    #[test_case(manifold)]
+    #[test_case(junctions)]
+    #[test_case(polygon)]
+    #[test_case(machines)]
+    #[test_case(small_machines)]
+    #[test_case(devices)]
+    #[test_case(regions)]
+    fn same_seed_same_input(generate: Generator) {
+        assert_eq!(generate(20, 3), generate(20, 3));
+        assert_ne!(generate(20, 3), generate(20, 4));
+    }
+}
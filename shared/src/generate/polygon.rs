@@ -0,0 +1,128 @@
+use super::Rng;
+
+/// Day 9: the corners of a rectilinear polygon with about `4 * size` corners, listed in order.
+///
+/// The polygon is made of `size` columns whose spans overlap their neighbours. About a third of
+/// the columns and levels sit right next to the one before, so parallel edges also run side by
+/// side with no tile between them.
+pub fn polygon(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let columns = size.max(1);
+    let levels = (columns as u64 * 2).max(4);
+
+    let mut bottoms = vec![rng.range(0, levels - 1)];
+    let mut tops = vec![rng.range(bottoms[0] + 1, levels)];
+    for i in 1..columns {
+        let bottom = rng.range(0, tops[i - 1] - 1);
+        let top = rng.range(bottom.max(bottoms[i - 1]) + 1, levels);
+        bottoms.push(bottom);
+        tops.push(top);
+    }
+
+    let spread = (100_000 / (levels + 1)).max(1);
+    let xs = coordinates(&mut rng, columns + 1, spread);
+    let ys = coordinates(&mut rng, levels as usize + 1, spread);
+
+    let mut corners = Vec::with_capacity(columns * 4);
+    for i in 0..columns {
+        corners.push((xs[i], ys[tops[i] as usize]));
+        corners.push((xs[i + 1], ys[tops[i] as usize]));
+    }
+    for i in (0..columns).rev() {
+        corners.push((xs[i + 1], ys[bottoms[i] as usize]));
+        corners.push((xs[i], ys[bottoms[i] as usize]));
+    }
+
+    let mut corners = simplify(corners);
+    let start = rng.below(corners.len());
+    corners.rotate_left(start);
+    if rng.chance(1, 2) {
+        corners.reverse();
+    }
+    if rng.chance(1, 2) {
+        corners.iter_mut().for_each(|(x, y)| (*x, *y) = (*y, *x));
+    }
+
+    corners
+        .iter()
+        .map(|(x, y)| format!("{},{}\n", x, y))
+        .collect()
+}
+
+/// Increasing coordinates, a third of them just one above the last and the rest at least two.
+fn coordinates(rng: &mut Rng, count: usize, spread: u64) -> Vec<u64> {
+    let mut value = 0;
+    (0..count)
+        .map(|_| {
+            value += match rng.chance(1, 3) {
+                true => 1,
+                false => rng.range(2, 2 + spread),
+            };
+            value
+        })
+        .collect()
+}
+
+/// Drops repeated points and points in the middle of a straight edge.
+fn simplify(mut corners: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        let mut i = 0;
+        while i < corners.len() {
+            let len = corners.len();
+            let previous = corners[(i + len - 1) % len];
+            let current = corners[i];
+            let next = corners[(i + 1) % len];
+
+            let straight = (previous.0 == current.0 && current.0 == next.0)
+                || (previous.1 == current.1 && current.1 == next.1);
+            if current == next || straight {
+                corners.remove(i);
+                changed = true;
+            } else {
+                i += 1;
+            }
+        }
+    }
+    corners
+}
+
+#[cfg(test)]
+mod polygon_tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn corners(size: usize, seed: u64) -> Vec<(u64, u64)> {
+        polygon(size, seed)
+            .lines()
+            .map(|line| {
+                let (x, y) = line.split_once(',').unwrap();
+                (x.parse().unwrap(), y.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[test_case(1, 1)]
+    #[test_case(5, 2)]
+    #[test_case(50, 3)]
+    fn consecutive_corners_share_a_row_or_column(size: usize, seed: u64) {
+        let corners = corners(size, seed);
+
+        assert!(corners.len() >= 4);
+        assert!(corners.len().is_multiple_of(2));
+        for (i, &(x, y)) in corners.iter().enumerate() {
+            let (next_x, next_y) = corners[(i + 1) % corners.len()];
+            assert!((x == next_x) != (y == next_y));
+        }
+    }
+
+    #[test]
+    fn some_parallel_edges_are_side_by_side() {
+        let corners = corners(50, 3);
+        let mut xs: Vec<u64> = corners.iter().map(|&(x, _)| x).collect();
+        xs.sort();
+        xs.dedup();
+        assert!(xs.windows(2).any(|pair| pair[1] == pair[0] + 1));
+    }
+}
@@ -0,0 +1,53 @@
+use super::Rng;
+
+/// Day 2: `size` disjoint ID ranges in random order, some of them crossing a power of ten.
+pub fn ranges(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut ranges = Vec::with_capacity(size);
+    let mut start = rng.range(1, 20);
+
+    for _ in 0..size {
+        let digits = (start.ilog10() + 1) as u64;
+        let width = 10u64.pow(rng.range(1, digits.div_ceil(2)) as u32);
+        let end = start + rng.range(0, width);
+        ranges.push((start, end));
+
+        let next = 10u64.pow(digits as u32);
+        start = if rng.chance(1, 3) && digits < 10 && end + 1 < next {
+            (end + 1).max(next - rng.range(1, width))
+        } else {
+            end + rng.range(1, width.max(10) * 10)
+        };
+    }
+
+    rng.shuffle(&mut ranges);
+    ranges
+        .iter()
+        .map(|(start, end)| format!("{}-{}", start, end))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod ranges_tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(1)]
+    #[test_case(2)]
+    #[test_case(3)]
+    fn ranges_are_disjoint(seed: u64) {
+        let mut ranges: Vec<(u64, u64)> = ranges(40, seed)
+            .split(',')
+            .map(|range| {
+                let (start, end) = range.split_once('-').unwrap();
+                (start.parse().unwrap(), end.parse().unwrap())
+            })
+            .collect();
+        ranges.sort();
+
+        assert_eq!(ranges.len(), 40);
+        assert!(ranges.iter().all(|(start, end)| start <= end));
+        assert!(ranges.windows(2).all(|pair| pair[0].1 < pair[1].0));
+    }
+}
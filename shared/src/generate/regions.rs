@@ -0,0 +1,87 @@
+use super::Rng;
+
+/// Day 12: six presents and `size` regions under the trees.
+///
+/// Besides the puzzle's regions, which either have a 3x3 spot for each present or less room
+/// than the presents cover, a third of them are small and tight: the presents cover no more than
+/// the region, but fit only when turned and interlocked, if at all.
+pub fn regions(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+    let mut areas = [0; 6];
+
+    for (i, area) in areas.iter_mut().enumerate() {
+        let mut cells = [false; 9];
+        let count = rng.range(5, 7) as usize;
+        cells[..count].fill(true);
+        rng.shuffle(&mut cells);
+        *area = count as u64;
+
+        input.push_str(&format!("{}:\n", i));
+        for row in cells.chunks(3) {
+            input.extend(row.iter().map(|&cell| if cell { '#' } else { '.' }));
+            input.push('\n');
+        }
+        input.push('\n');
+    }
+
+    for _ in 0..size {
+        let kind = rng.below(3);
+        // Tight regions are kept small, as checking them takes a backtracking search.
+        let (width, height) = match kind {
+            2 => (rng.range(3, 6), rng.range(3, 5)),
+            _ => (rng.range(3, 8), rng.range(3, 8)),
+        };
+        let mut counts = [0; 6];
+        let mut covered = 0;
+        match kind {
+            0 => {
+                for _ in 0..rng.range(0, (width / 3) * (height / 3)) {
+                    counts[rng.below(6)] += 1;
+                }
+            }
+            1 => {
+                while covered <= width * height {
+                    let i = rng.below(6);
+                    counts[i] += 1;
+                    covered += areas[i];
+                }
+            }
+            _ => loop {
+                let i = rng.below(6);
+                if covered + areas[i] > width * height {
+                    break;
+                }
+                counts[i] += 1;
+                covered += areas[i];
+            },
+        }
+
+        let counts: Vec<String> = counts.iter().map(|count| count.to_string()).collect();
+        input.push_str(&format!("{}x{}: {}\n", width, height, counts.join(" ")));
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod regions_tests {
+    use super::*;
+
+    #[test]
+    fn shapes_then_regions() {
+        let input = regions(10, 9);
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 6 * 5 + 10);
+        for (i, shape) in lines[..30].chunks(5).enumerate() {
+            assert_eq!(shape[0], format!("{}:", i));
+            assert!(shape[1..4].iter().all(|row| row.len() == 3));
+            assert_eq!(shape[4], "");
+        }
+        for region in &lines[30..] {
+            let (size, counts) = region.split_once(": ").unwrap();
+            assert!(size.contains('x'));
+            assert_eq!(counts.split(' ').count(), 6);
+        }
+    }
+}
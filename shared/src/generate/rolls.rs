@@ -0,0 +1,29 @@
+use super::Rng;
+
+/// Day 4: a `size` by `size` grid where about two in three cells hold a roll of paper.
+pub fn rolls(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        input.extend((0..size).map(|_| if rng.chance(2, 3) { '@' } else { '.' }));
+        input.push('\n');
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod rolls_tests {
+    use super::*;
+
+    #[test]
+    fn grid_is_square() {
+        let input = rolls(12, 4);
+        assert_eq!(input.lines().count(), 12);
+        for line in input.lines() {
+            assert_eq!(line.len(), 12);
+            assert!(line.bytes().all(|b| b == b'@' || b == b'.'));
+        }
+    }
+}
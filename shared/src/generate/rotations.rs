@@ -0,0 +1,43 @@
+use super::Rng;
+
+/// Day 1: `size` rotations of a dial that starts at 50, landing on 0 every so often.
+pub fn rotations(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut dial: i64 = 50;
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let right = rng.chance(1, 2);
+        let mut distance = rng.range(1, 999) as i64;
+        if rng.chance(1, 4) {
+            let to_zero = if right { 100 - dial } else { dial };
+            distance = to_zero + 100 * rng.range(0, 3) as i64;
+            if distance == 0 {
+                distance = 100;
+            }
+        }
+
+        dial = (dial + if right { distance } else { -distance }).rem_euclid(100);
+        input.push(if right { 'R' } else { 'L' });
+        input.push_str(&distance.to_string());
+        input.push('\n');
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod rotations_tests {
+    use super::*;
+
+    #[test]
+    fn every_line_is_a_rotation() {
+        let input = rotations(50, 1);
+        assert_eq!(input.lines().count(), 50);
+        for line in input.lines() {
+            let (direction, distance) = line.split_at(1);
+            assert!(direction == "L" || direction == "R");
+            assert!(distance.parse::<u32>().unwrap() > 0);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use super::Rng;
+
+/// Day 6: `size` problems of three or four numbers side by side, each above its operator.
+///
+/// Numbers are padded on either side within their problem, and no digit is zero, since a
+/// column holding only spaces and zeros could not be told apart from a gap between problems.
+pub fn worksheet(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let rows = rng.range(3, 4) as usize;
+    let mut lines = vec![String::new(); rows + 1];
+
+    for problem in 0..size {
+        let numbers: Vec<String> = (0..rows)
+            .map(|_| {
+                let digits = rng.range(1, 4);
+                (0..digits)
+                    .map(|_| (b'0' + rng.range(1, 9) as u8) as char)
+                    .collect()
+            })
+            .collect();
+        let width = numbers.iter().map(String::len).max().unwrap_or(1);
+        let left = rng.chance(1, 2);
+        let operator = if rng.chance(1, 2) { '*' } else { '+' };
+
+        if problem > 0 {
+            lines.iter_mut().for_each(|line| line.push(' '));
+        }
+        for (line, number) in lines.iter_mut().zip(&numbers) {
+            if left {
+                line.push_str(&format!("{:<width$}", number));
+            } else {
+                line.push_str(&format!("{:>width$}", number));
+            }
+        }
+        lines[rows].push_str(&format!("{:<width$}", operator));
+    }
+
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+#[cfg(test)]
+mod worksheet_tests {
+    use super::*;
+
+    #[test]
+    fn rows_line_up() {
+        let input = worksheet(20, 6);
+        let lines: Vec<&str> = input.lines().collect();
+        assert!((4..=5).contains(&lines.len()));
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+        let operators = lines.last().unwrap();
+        assert_eq!(operators.matches(['*', '+']).count(), 20);
+        assert!(operators.starts_with(['*', '+']));
+    }
+}
@@ -1,35 +1,72 @@
-use crate::points::{directions::Direction, point::Point};
+use std::array;
 
-pub const DIAGONAL: [(isize, isize); 4] = [(-1, 1), (1, -1), (1, 1), (-1, -1)];
+use crate::points::{directions::Direction, ipoint::IPoint, point::Point, traits::ModuloPositive};
 
-pub struct Diagonals {
-    pub x: usize,
-    pub y: usize,
-    pub height: usize,
-    pub width: usize,
-    pub current: usize,
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Edges {
+    /// Steps off the grid lead nowhere.
+    Bounded,
+    /// Steps off one edge come back in on the opposite one.
+    Wrapping,
 }
 
-impl Iterator for Diagonals {
-    type Item = Point;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.current > 3 {
-                return None;
+impl Edges {
+    pub fn step(
+        self,
+        point: Point,
+        direction: Direction,
+        width: usize,
+        height: usize,
+    ) -> Option<Point> {
+        let offset = direction.offset();
+        match self {
+            Edges::Bounded => {
+                let x = point
+                    .x
+                    .checked_add_signed(offset.x)
+                    .filter(|&x| x < width)?;
+                let y = point
+                    .y
+                    .checked_add_signed(offset.y)
+                    .filter(|&y| y < height)?;
+                Some(Point::new(x, y))
+            }
+            Edges::Wrapping => {
+                if width == 0 || height == 0 {
+                    return None;
+                }
+                let size = IPoint::new(width as isize, height as isize);
+                let moved = (IPoint::from(point) + offset).modulo_positive(size);
+                Some(Point::new(moved.x as usize, moved.y as usize))
             }
+        }
+    }
+}
 
-            let nx = self.x as isize + DIAGONAL[self.current].0;
-            let ny = self.y as isize + DIAGONAL[self.current].1;
+pub struct Neighbours<I> {
+    pub point: Point,
+    pub width: usize,
+    pub height: usize,
+    pub edges: Edges,
+    pub directions: I,
+}
 
-            self.current += 1;
+impl<I> Iterator for Neighbours<I>
+where
+    I: Iterator<Item = Direction>,
+{
+    type Item = (Direction, Point);
 
-            if nx < 0 || nx >= self.width as isize || ny < 0 || ny >= self.height as isize {
-                continue;
+    fn next(&mut self) -> Option<Self::Item> {
+        for direction in self.directions.by_ref() {
+            if let Some(point) = self
+                .edges
+                .step(self.point, direction, self.width, self.height)
+            {
+                return Some((direction, point));
             }
-
-            return Some(Point::new(nx as usize, ny as usize));
         }
+        None
     }
 }
 
@@ -60,148 +97,36 @@ impl Iterator for Positions {
     }
 }
 
-pub const ADJ_EIGHT: [(isize, isize); 8] = [
-    (-1, 1),
-    (-1, 0),
-    (-1, -1),
-    (0, -1),
-    (0, 1),
-    (1, 1),
-    (1, 0),
-    (1, -1),
-];
-
-pub struct AdjecentEight {
-    pub x: usize,
-    pub y: usize,
-    pub height: usize,
-    pub width: usize,
-    pub current: usize,
-}
-
-impl Iterator for AdjecentEight {
-    type Item = Point;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.current > 7 {
-                return None;
-            }
-
-            let nx = self.x as isize + ADJ_EIGHT[self.current].0;
-            let ny = self.y as isize + ADJ_EIGHT[self.current].1;
-
-            self.current += 1;
-
-            if nx < 0 || nx >= self.width as isize || ny < 0 || ny >= self.height as isize {
-                continue;
-            }
-
-            return Some(Point::new(nx as usize, ny as usize));
-        }
-    }
-}
-
-pub const ADJ_FOUR: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-
-pub struct AdjacentFour {
-    pub x: usize,
-    pub y: usize,
-    pub height: usize,
-    pub width: usize,
-    pub current: usize,
-}
-
-impl Iterator for AdjacentFour {
-    type Item = Point;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.current > 3 {
-                return None;
-            }
-
-            let nx = self.x as isize + ADJ_FOUR[self.current].0;
-            let ny = self.y as isize + ADJ_FOUR[self.current].1;
+macro_rules! points_preset {
+    ($name:ident, $count:literal) => {
+        pub struct $name(pub Neighbours<array::IntoIter<Direction, $count>>);
 
-            self.current += 1;
+        impl Iterator for $name {
+            type Item = Point;
 
-            if nx < 0 || nx >= self.width as isize || ny < 0 || ny >= self.height as isize {
-                continue;
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next().map(|(_, point)| point)
             }
-
-            return Some(Point::new(nx as usize, ny as usize));
         }
-    }
+    };
 }
 
-pub struct AdjacentThreeInDirection {
-    pub x: usize,
-    pub y: usize,
-    pub height: usize,
-    pub width: usize,
-    pub current: usize,
-    pub direction: Direction,
-}
-
-impl Iterator for AdjacentThreeInDirection {
-    type Item = (Point, Direction);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.current > 2 {
-                return None;
-            }
-
-            self.direction = self.direction.rotate_90();
-            let nx = self.x as isize + ADJ_FOUR[self.direction as usize].0;
-            let ny = self.y as isize + ADJ_FOUR[self.direction as usize].1;
+macro_rules! directional_preset {
+    ($name:ident, $count:literal) => {
+        pub struct $name(pub Neighbours<array::IntoIter<Direction, $count>>);
 
-            self.current += 1;
+        impl Iterator for $name {
+            type Item = (Point, Direction);
 
-            if nx < 0 || nx >= self.width as isize || ny < 0 || ny >= self.height as isize {
-                continue;
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next().map(|(direction, point)| (point, direction))
             }
-
-            return Some((Point::new(nx as usize, ny as usize), self.direction));
         }
-    }
-}
-
-pub struct AdjacentFourDirectional {
-    pub x: usize,
-    pub y: usize,
-    pub height: usize,
-    pub width: usize,
-    pub current: usize,
+    };
 }
 
-impl Iterator for AdjacentFourDirectional {
-    type Item = (Point, Direction);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if self.current > 3 {
-                return None;
-            }
-
-            let nx = self.x as isize + ADJ_FOUR[self.current].0;
-            let ny = self.y as isize + ADJ_FOUR[self.current].1;
-            let dir = match self.current {
-                0 => Direction::North,
-                1 => Direction::East,
-                2 => Direction::South,
-                3 => Direction::West,
-                _ => panic!(),
-            };
-
-            self.current += 1;
-
-            if nx < 0 || nx >= self.width as isize || ny < 0 || ny >= self.height as isize {
-                continue;
-            }
-
-            return Some((Point::new(nx as usize, ny as usize), dir));
-        }
-    }
-}
+points_preset!(AdjecentEight, 8);
+points_preset!(AdjacentFour, 4);
+points_preset!(Diagonals, 4);
+directional_preset!(AdjacentFourDirectional, 4);
+directional_preset!(AdjacentThreeInDirection, 3);
@@ -2,7 +2,15 @@ use std::ops::Index;
 
 use iterators::*;
 
-use crate::points::{directions::Direction, iline::ILine, ipoint::IPoint, point::Point};
+use crate::{
+    error::SolveError,
+    points::{
+        directions::{CARDINALS, DIRECTIONS, Direction, ORDINALS},
+        iline::ILine,
+        ipoint::IPoint,
+        point::Point,
+    },
+};
 
 #[derive(Clone, Debug)]
 pub struct Grid<T> {
@@ -57,6 +65,64 @@ impl<T> Grid<T> {
         Self { vec, width, height }
     }
 
+    /// One cell per byte, one row per line, all rows as wide as the first.
+    pub fn parse(input: &str, map: impl FnMut(u8) -> T) -> Result<Self, SolveError> {
+        Self::parse_with_markers(input, [], map).map(|(grid, [])| grid)
+    }
+
+    /// Like `parse`, also returning where each of `markers` is, which must appear exactly once.
+    /// Marker bytes are still passed to `map`.
+    pub fn parse_with_markers<const N: usize>(
+        input: &str,
+        markers: [u8; N],
+        mut map: impl FnMut(u8) -> T,
+    ) -> Result<(Self, [Point; N]), SolveError> {
+        let mut vec = Vec::with_capacity(input.len());
+        let mut found = [None; N];
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let row = line.len();
+            let expected = *width.get_or_insert(row);
+            if row != expected {
+                return Err(SolveError::at(
+                    y + 1,
+                    row.min(expected) + 1,
+                    format!("row is {} wide but the first is {}", row, expected),
+                ));
+            }
+
+            for (x, byte) in line.bytes().enumerate() {
+                if let Some(marker) = markers.iter().position(|&marker| marker == byte) {
+                    if found[marker].is_some() {
+                        return Err(SolveError::at(
+                            y + 1,
+                            x + 1,
+                            format!("more than one '{}'", byte.escape_ascii()),
+                        ));
+                    }
+                    found[marker] = Some(Point::new(x, y));
+                }
+                vec.push(map(byte));
+            }
+            height += 1;
+        }
+
+        let width = match width {
+            Some(width) if width > 0 => width,
+            _ => return Err(SolveError::new("empty input")),
+        };
+
+        let mut positions = [Point::new(0, 0); N];
+        for (i, position) in found.into_iter().enumerate() {
+            positions[i] = position
+                .ok_or_else(|| SolveError::new(format!("no '{}'", markers[i].escape_ascii())))?;
+        }
+
+        Ok((Self { vec, width, height }, positions))
+    }
+
     pub fn overwrite(&mut self, vec: Vec<T>) -> bool {
         if self.vec.len() == vec.len() {
             self.vec = vec;
@@ -100,157 +166,75 @@ impl<T> Grid<T> {
     }
 
     pub fn go(&self, point: Point, direction: Direction) -> Option<Point> {
-        match direction {
-            Direction::North => self.up(point),
-            Direction::NorthEast => self.up_right(point),
-            Direction::East => self.right(point),
-            Direction::SouthEast => self.down_right(point),
-            Direction::South => self.down(point),
-            Direction::SouthWest => self.down_left(point),
-            Direction::West => self.left(point),
-            Direction::NorthWest => self.up_left(point),
-        }
+        Edges::Bounded.step(point, direction, self.width, self.height)
     }
 
     pub fn go_xy(&self, x: usize, y: usize, direction: Direction) -> Option<Point> {
-        match direction {
-            Direction::North => self.up_xy(x, y),
-            Direction::NorthEast => self.up_right_xy(x, y),
-            Direction::East => self.right_xy(x, y),
-            Direction::SouthEast => self.down_right_xy(x, y),
-            Direction::South => self.down_xy(x, y),
-            Direction::SouthWest => self.down_left_xy(x, y),
-            Direction::West => self.left_xy(x, y),
-            Direction::NorthWest => self.up_left_xy(x, y),
-        }
+        self.go(Point::new(x, y), direction)
     }
 
     pub fn up_left(&self, point: Point) -> Option<Point> {
-        if point.y == 0 || point.x == 0 {
-            return None;
-        }
-
-        Some(Point::new(point.x - 1, point.y - 1))
+        self.go(point, Direction::NorthWest)
     }
 
     pub fn up_right(&self, point: Point) -> Option<Point> {
-        if point.y == 0 || point.x >= self.width - 1 {
-            return None;
-        }
-
-        Some(Point::new(point.x + 1, point.y - 1))
+        self.go(point, Direction::NorthEast)
     }
 
     pub fn down_left(&self, point: Point) -> Option<Point> {
-        if point.y >= self.height - 1 || point.x == 0 {
-            return None;
-        }
-
-        Some(Point::new(point.x - 1, point.y + 1))
+        self.go(point, Direction::SouthWest)
     }
 
     pub fn down_right(&self, point: Point) -> Option<Point> {
-        if point.y >= self.height - 1 || point.x >= self.width - 1 {
-            return None;
-        }
-
-        Some(Point::new(point.x + 1, point.y + 1))
+        self.go(point, Direction::SouthEast)
     }
 
     pub fn up(&self, point: Point) -> Option<Point> {
-        if point.y == 0 {
-            return None;
-        }
-
-        Some(Point::new(point.x, point.y - 1))
+        self.go(point, Direction::North)
     }
 
     pub fn down(&self, point: Point) -> Option<Point> {
-        if point.y >= self.height - 1 {
-            return None;
-        }
-
-        Some(Point::new(point.x, point.y + 1))
+        self.go(point, Direction::South)
     }
 
     pub fn right(&self, point: Point) -> Option<Point> {
-        if point.x >= self.width - 1 {
-            return None;
-        }
-
-        Some(Point::new(point.x + 1, point.y))
+        self.go(point, Direction::East)
     }
 
     pub fn left(&self, point: Point) -> Option<Point> {
-        if point.x == 0 {
-            return None;
-        }
-
-        Some(Point::new(point.x - 1, point.y))
+        self.go(point, Direction::West)
     }
 
     pub fn up_left_xy(&self, x: usize, y: usize) -> Option<Point> {
-        if y == 0 || x == 0 {
-            return None;
-        }
-
-        Some(Point::new(x - 1, y - 1))
+        self.go(Point::new(x, y), Direction::NorthWest)
     }
 
     pub fn up_right_xy(&self, x: usize, y: usize) -> Option<Point> {
-        if y == 0 || x >= self.width - 1 {
-            return None;
-        }
-
-        Some(Point::new(x + 1, y - 1))
+        self.go(Point::new(x, y), Direction::NorthEast)
     }
 
     pub fn down_left_xy(&self, x: usize, y: usize) -> Option<Point> {
-        if y >= self.height - 1 || x == 0 {
-            return None;
-        }
-
-        Some(Point::new(x - 1, y + 1))
+        self.go(Point::new(x, y), Direction::SouthWest)
     }
 
     pub fn down_right_xy(&self, x: usize, y: usize) -> Option<Point> {
-        if y >= self.height - 1 || x >= self.width - 1 {
-            return None;
-        }
-
-        Some(Point::new(x + 1, y + 1))
+        self.go(Point::new(x, y), Direction::SouthEast)
     }
 
     pub fn up_xy(&self, x: usize, y: usize) -> Option<Point> {
-        if y == 0 {
-            return None;
-        }
-
-        Some(Point::new(x, y - 1))
+        self.go(Point::new(x, y), Direction::North)
     }
 
     pub fn down_xy(&self, x: usize, y: usize) -> Option<Point> {
-        if y >= self.height - 1 {
-            return None;
-        }
-
-        Some(Point::new(x, y + 1))
+        self.go(Point::new(x, y), Direction::South)
     }
 
     pub fn right_xy(&self, x: usize, y: usize) -> Option<Point> {
-        if x >= self.width - 1 {
-            return None;
-        }
-
-        Some(Point::new(x + 1, y))
+        self.go(Point::new(x, y), Direction::East)
     }
 
     pub fn left_xy(&self, x: usize, y: usize) -> Option<Point> {
-        if x == 0 {
-            return None;
-        }
-
-        Some(Point::new(x - 1, y))
+        self.go(Point::new(x, y), Direction::West)
     }
 
     pub fn points(&self) -> Positions {
@@ -262,59 +246,55 @@ impl<T> Grid<T> {
         }
     }
 
-    pub fn adjacent_eight(&self, point: Point) -> AdjecentEight {
-        AdjecentEight {
-            x: point.x,
-            y: point.y,
-            height: self.height,
+    /// Each step in `directions` that stays on the grid, with where it leads.
+    pub fn neighbours<I>(&self, point: Point, directions: I) -> Neighbours<I::IntoIter>
+    where
+        I: IntoIterator<Item = Direction>,
+    {
+        self.neighbours_with(point, directions, Edges::Bounded)
+    }
+
+    pub fn neighbours_with<I>(
+        &self,
+        point: Point,
+        directions: I,
+        edges: Edges,
+    ) -> Neighbours<I::IntoIter>
+    where
+        I: IntoIterator<Item = Direction>,
+    {
+        Neighbours {
+            point,
             width: self.width,
-            current: 0,
+            height: self.height,
+            edges,
+            directions: directions.into_iter(),
         }
     }
 
+    pub fn adjacent_eight(&self, point: Point) -> AdjecentEight {
+        AdjecentEight(self.neighbours(point, DIRECTIONS))
+    }
+
     pub fn adjacent_four(&self, point: Point) -> AdjacentFour {
-        AdjacentFour {
-            x: point.x,
-            y: point.y,
-            height: self.height,
-            width: self.width,
-            current: 0,
-        }
+        AdjacentFour(self.neighbours(point, CARDINALS))
     }
 
     pub fn adjacent_four_directional(&self, point: Point) -> AdjacentFourDirectional {
-        AdjacentFourDirectional {
-            x: point.x,
-            y: point.y,
-            height: self.height,
-            width: self.width,
-            current: 0,
-        }
+        AdjacentFourDirectional(self.neighbours(point, CARDINALS))
     }
 
+    /// The cardinal steps other than back the way `from` came.
     pub fn adjacent_three_in_direction(
         &self,
         point: Point,
         from: Direction,
     ) -> AdjacentThreeInDirection {
-        AdjacentThreeInDirection {
-            x: point.x,
-            y: point.y,
-            height: self.height,
-            width: self.width,
-            current: 0,
-            direction: from.reverse(),
-        }
+        AdjacentThreeInDirection(self.neighbours(point, from.reverse().other_cardinals()))
     }
 
     pub fn diagonals(&self, point: Point) -> Diagonals {
-        Diagonals {
-            x: point.x,
-            y: point.y,
-            height: self.height,
-            width: self.width,
-            current: 0,
-        }
+        Diagonals(self.neighbours(point, ORDINALS))
     }
 }
 
@@ -477,17 +457,7 @@ impl Grid<bool> {
     }
 
     pub fn go_if_true(&self, point: Point, direction: Direction) -> Option<Point> {
-        (match direction {
-            Direction::North => self.up(point),
-            Direction::NorthEast => self.up_right(point),
-            Direction::East => self.right(point),
-            Direction::SouthEast => self.down_right(point),
-            Direction::South => self.down(point),
-            Direction::SouthWest => self.down_left(point),
-            Direction::West => self.left(point),
-            Direction::NorthWest => self.up_left(point),
-        })
-        .and_then(|p| if self[p] { Some(p) } else { None })
+        self.go(point, direction).filter(|&p| self[p])
     }
 }
 
@@ -519,3 +489,190 @@ where
 }
 
 pub mod iterators;
+pub mod sparse;
+pub mod wrapping;
+
+#[cfg(test)]
+mod grid_tests {
+    use super::*;
+    use crate::{generate::Rng, points::traits::ModuloPositive};
+    use test_case::test_case;
+
+    /// Every point of random grids down to a single cell, so all edges and corners get visited.
+    fn positions(seed: u64) -> impl Iterator<Item = (Grid<()>, Point)> {
+        let mut rng = Rng::new(seed);
+        (0..50).flat_map(move |_| {
+            let grid = Grid::filled((), rng.range(1, 6) as usize, rng.range(1, 6) as usize);
+            grid.points()
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(move |point| (grid.clone(), point))
+        })
+    }
+
+    fn go_all(grid: &Grid<()>, point: Point, directions: &[Direction]) -> Vec<Point> {
+        directions
+            .iter()
+            .filter_map(|direction| grid.go(point, *direction))
+            .collect()
+    }
+
+    #[test]
+    fn go_agrees_with_point_go() {
+        for (grid, point) in positions(0) {
+            for direction in DIRECTIONS {
+                // Shifted away from zero so that stepping off the top or left edge can't underflow.
+                let one = Point::new(1, 1);
+                let expected = (point + one)
+                    .go(direction)
+                    .checked_sub(one)
+                    .filter(|moved| grid.is_within_bounds(*moved));
+                assert_eq!(
+                    grid.go(point, direction),
+                    expected,
+                    "{} {}",
+                    point,
+                    direction
+                );
+                assert_eq!(grid.go_xy(point.x, point.y, direction), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn adjacent_four_is_every_cardinal_step() {
+        for (grid, point) in positions(1) {
+            let adjacent: Vec<_> = grid.adjacent_four(point).collect();
+            assert_eq!(adjacent, go_all(&grid, point, &CARDINALS));
+        }
+    }
+
+    #[test]
+    fn adjacent_four_directional_names_the_step() {
+        for (grid, point) in positions(2) {
+            let adjacent: Vec<_> = grid.adjacent_four_directional(point).collect();
+            assert_eq!(adjacent.len(), go_all(&grid, point, &CARDINALS).len());
+            for (neighbour, direction) in adjacent {
+                assert_eq!(grid.go(point, direction), Some(neighbour));
+            }
+        }
+    }
+
+    #[test]
+    fn adjacent_eight_is_every_step() {
+        for (grid, point) in positions(3) {
+            let mut adjacent: Vec<_> = grid.adjacent_eight(point).collect();
+            let mut expected = go_all(&grid, point, &DIRECTIONS);
+            adjacent.sort();
+            expected.sort();
+            assert_eq!(adjacent, expected, "{}", point);
+        }
+    }
+
+    #[test]
+    fn diagonals_are_the_steps_between_cardinals() {
+        for (grid, point) in positions(4) {
+            let mut diagonals: Vec<_> = grid.diagonals(point).collect();
+            let mut expected = go_all(&grid, point, &ORDINALS);
+            diagonals.sort();
+            expected.sort();
+            assert_eq!(diagonals, expected, "{}", point);
+        }
+    }
+
+    #[test]
+    fn adjacent_three_never_turns_back() {
+        for (grid, point) in positions(5) {
+            for from in CARDINALS {
+                let back = from.reverse();
+                let adjacent: Vec<_> = grid.adjacent_three_in_direction(point, from).collect();
+                assert_eq!(
+                    adjacent.len(),
+                    go_all(&grid, point, &back.other_cardinals()).len()
+                );
+                for (neighbour, direction) in adjacent {
+                    assert_ne!(direction, back);
+                    assert_eq!(grid.go(point, direction), Some(neighbour));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn offset_agrees_with_point_go() {
+        let point = Point::new(1, 1);
+        for direction in DIRECTIONS {
+            assert_eq!(
+                Point::try_from(IPoint::from(point) + direction.offset()),
+                Ok(point.go(direction))
+            );
+        }
+    }
+
+    #[test]
+    fn neighbours_pair_each_step_with_its_direction() {
+        let mut rng = Rng::new(6);
+        for (grid, point) in positions(6) {
+            let mut directions = DIRECTIONS;
+            rng.shuffle(&mut directions);
+            let directions = &directions[..rng.range(0, 8) as usize];
+
+            let neighbours: Vec<_> = grid.neighbours(point, directions.iter().copied()).collect();
+            let expected: Vec<_> = directions
+                .iter()
+                .filter_map(|&direction| Some((direction, grid.go(point, direction)?)))
+                .collect();
+            assert_eq!(neighbours, expected);
+        }
+    }
+
+    #[test]
+    fn wrapping_neighbours_always_step() {
+        for (grid, point) in positions(7) {
+            let size = IPoint::new(grid.width as isize, grid.height as isize);
+            let neighbours: Vec<_> = grid
+                .neighbours_with(point, DIRECTIONS, Edges::Wrapping)
+                .collect();
+            assert_eq!(neighbours.len(), DIRECTIONS.len());
+            for (direction, neighbour) in neighbours {
+                let moved = (IPoint::from(point) + direction.offset()).modulo_positive(size);
+                assert_eq!(IPoint::from(neighbour), moved);
+                if let Some(bounded) = grid.go(point, direction) {
+                    assert_eq!(neighbour, bounded);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_maps_every_byte() {
+        let grid = Grid::parse("#.#\n..#\n", |byte| byte == b'#').unwrap();
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid.vec, [true, false, true, false, false, true]);
+    }
+
+    #[test]
+    fn parse_with_markers_finds_them() {
+        let (grid, [start, end]) =
+            Grid::parse_with_markers("#S.\n.E#", *b"SE", |byte| byte != b'#').unwrap();
+        assert_eq!((start, end), (Point::new(1, 0), Point::new(1, 1)));
+        assert!(grid[start] && grid[end]);
+    }
+
+    #[test_case("", "empty input")]
+    #[test_case("\n\n", "empty input")]
+    #[test_case("...\n..\n", "line 2, column 3: row is 2 wide but the first is 3")]
+    #[test_case("..\n...", "line 2, column 3: row is 3 wide but the first is 2")]
+    #[test_case("..\n\n..", "line 2, column 1: row is 0 wide but the first is 2")]
+    fn parse_rejects(input: &str, expected: &str) {
+        let error = Grid::parse(input, |byte| byte).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[test_case(".S.\n...", "no 'E'")]
+    #[test_case("SE\n.S", "line 2, column 2: more than one 'S'")]
+    fn parse_with_markers_rejects(input: &str, expected: &str) {
+        let error = Grid::parse_with_markers(input, *b"SE", |byte| byte).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+    }
+}
@@ -0,0 +1,319 @@
+use std::ops::Index;
+
+use fxhash::FxHashMap as HashMap;
+
+use super::Grid;
+use crate::points::{directions::Direction, ipoint::IPoint, point::Point};
+
+/// Cells at any signed coordinate, stored only where something was inserted.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<IPoint, T>,
+    bounds: Option<(IPoint, IPoint)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::default(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest and largest corner of the box around every cell, both inclusive.
+    pub fn bounds(&self) -> Option<(IPoint, IPoint)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.x - min.x) as usize + 1)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.y - min.y) as usize + 1)
+    }
+
+    pub fn get(&self, point: IPoint) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: IPoint) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn contains(&self, point: IPoint) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn insert(&mut self, point: IPoint, value: T) -> Option<T> {
+        self.grow(point);
+        self.cells.insert(point, value)
+    }
+
+    pub fn get_or_insert_with(&mut self, point: IPoint, value: impl FnOnce() -> T) -> &mut T {
+        self.grow(point);
+        self.cells.entry(point).or_insert_with(value)
+    }
+
+    pub fn remove(&mut self, point: IPoint) -> Option<T> {
+        let removed = self.cells.remove(&point)?;
+        if let Some((min, max)) = self.bounds
+            && (point.x == min.x || point.y == min.y || point.x == max.x || point.y == max.y)
+        {
+            self.bounds = None;
+            for point in self.cells.keys().copied().collect::<Vec<_>>() {
+                self.grow(point);
+            }
+        }
+        Some(removed)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (IPoint, &T)> {
+        self.cells.iter().map(|(point, value)| (*point, value))
+    }
+
+    fn grow(&mut self, point: IPoint) {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                IPoint::new(min.x.min(point.x), min.y.min(point.y)),
+                IPoint::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+            None => (point, point),
+        });
+    }
+
+    pub fn go(&self, point: IPoint, direction: Direction) -> IPoint {
+        point + direction.offset()
+    }
+
+    /// Every step in `directions`, since the grid has no edges.
+    pub fn neighbours<I>(
+        &self,
+        point: IPoint,
+        directions: I,
+    ) -> impl Iterator<Item = (Direction, IPoint)> + use<T, I>
+    where
+        I: IntoIterator<Item = Direction>,
+    {
+        directions
+            .into_iter()
+            .map(move |direction| (direction, point + direction.offset()))
+    }
+
+    /// The steps in `directions` that lead to a cell, with its value.
+    pub fn occupied_neighbours<I>(
+        &self,
+        point: IPoint,
+        directions: I,
+    ) -> impl Iterator<Item = (Direction, IPoint, &T)>
+    where
+        I: IntoIterator<Item = Direction>,
+    {
+        self.neighbours(point, directions)
+            .filter_map(|(direction, point)| Some((direction, point, self.get(point)?)))
+    }
+
+    /// A dense copy covering the bounding box, and the offset to add to its points to get
+    /// back to this grid's coordinates.
+    pub fn to_grid(&self, fill: T) -> (Grid<T>, IPoint)
+    where
+        T: Clone,
+    {
+        let Some((min, _)) = self.bounds else {
+            return (Grid::new(Vec::new(), 0, 0), IPoint::new(0, 0));
+        };
+
+        let mut grid = Grid::filled(fill, self.height(), self.width());
+        for (point, value) in self.iter() {
+            let local = point - min;
+            grid[Point::new(local.x as usize, local.y as usize)] = value.clone();
+        }
+        (grid, min)
+    }
+}
+
+impl<T> Index<IPoint> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: IPoint) -> &T {
+        &self.cells[&index]
+    }
+}
+
+impl<T> FromIterator<(IPoint, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (IPoint, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (point, value) in iter {
+            grid.insert(point, value);
+        }
+        grid
+    }
+}
+
+impl<T> SparseGrid<T>
+where
+    T: std::fmt::Debug,
+{
+    pub fn print_debug(&self, unit_width: usize) -> String {
+        let mut str = String::new();
+        let Some((min, max)) = self.bounds else {
+            return str;
+        };
+
+        for y in min.y..=max.y {
+            str.push_str(&format!("{:4} ", y % 10000));
+            for x in min.x..=max.x {
+                match self.get(IPoint::new(x, y)) {
+                    Some(value) => str.push_str(&format!("{:width$?} ", value, width = unit_width)),
+                    None => str.push_str(&format!("{:width$} ", '.', width = unit_width)),
+                }
+            }
+            str.push('\n');
+        }
+        str.push_str("     ");
+        for x in min.x..=max.x {
+            str.push_str(&format!(
+                "{:<width$} ",
+                x.rem_euclid(10isize.pow(unit_width as u32)),
+                width = unit_width,
+            ));
+        }
+
+        str.push('\n');
+        str
+    }
+}
+
+impl SparseGrid<bool> {
+    pub fn print_bool(&self) -> String {
+        let mut str = String::new();
+        let Some((min, max)) = self.bounds else {
+            return str;
+        };
+
+        for y in min.y..=max.y {
+            str.push_str(&format!("{:4} ", y % 10000));
+            for x in min.x..=max.x {
+                str.push_str(match self.get(IPoint::new(x, y)) {
+                    Some(true) => "■ ",
+                    _ => ". ",
+                });
+            }
+            str.push('\n');
+        }
+        str
+    }
+}
+
+#[cfg(test)]
+mod sparse_tests {
+    use super::*;
+    use crate::points::directions::{CARDINALS, DIRECTIONS};
+    use test_case::test_case;
+
+    fn corners() -> SparseGrid<u8> {
+        [
+            (IPoint::new(-20000, 5), 1),
+            (IPoint::new(30000, 5), 2),
+            (IPoint::new(0, -7), 3),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn bounds_grow_with_inserts() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(IPoint::new(3, -4), ());
+        assert_eq!(
+            grid.bounds(),
+            Some((IPoint::new(3, -4), IPoint::new(3, -4)))
+        );
+        *grid.get_or_insert_with(IPoint::new(-1, 2), || ()) = ();
+        assert_eq!(
+            grid.bounds(),
+            Some((IPoint::new(-1, -4), IPoint::new(3, 2)))
+        );
+        assert_eq!((grid.width(), grid.height()), (5, 7));
+    }
+
+    #[test_case(IPoint::new(-20000, 5), Some((IPoint::new(0, -7), IPoint::new(30000, 5))))]
+    #[test_case(IPoint::new(0, -7), Some((IPoint::new(-20000, 5), IPoint::new(30000, 5))))]
+    #[test_case(IPoint::new(1, 1), Some((IPoint::new(-20000, -7), IPoint::new(30000, 5))))]
+    fn remove_shrinks_bounds(point: IPoint, expected: Option<(IPoint, IPoint)>) {
+        let mut grid = corners();
+        grid.remove(point);
+        assert_eq!(grid.bounds(), expected);
+    }
+
+    #[test]
+    fn removing_everything_leaves_no_bounds() {
+        let mut grid = corners();
+        for (point, _) in corners().iter() {
+            grid.remove(point);
+        }
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn neighbours_step_in_every_direction() {
+        let grid = corners();
+        let point = IPoint::new(-5, -5);
+        for (direction, neighbour) in grid.neighbours(point, DIRECTIONS) {
+            assert_eq!(neighbour - point, direction.offset());
+            assert_eq!(grid.go(point, direction), neighbour);
+        }
+    }
+
+    #[test]
+    fn occupied_neighbours_skip_empty_cells() {
+        let grid: SparseGrid<u8> = [(IPoint::new(0, -1), 1), (IPoint::new(1, 1), 2)]
+            .into_iter()
+            .collect();
+        let occupied: Vec<_> = grid
+            .occupied_neighbours(IPoint::new(0, 0), CARDINALS)
+            .collect();
+        assert_eq!(occupied, [(Direction::North, IPoint::new(0, -1), &1)]);
+    }
+
+    #[test]
+    fn to_grid_keeps_every_cell_at_its_offset() {
+        let grid: SparseGrid<u8> = [(IPoint::new(-2, 3), 1), (IPoint::new(1, 4), 2)]
+            .into_iter()
+            .collect();
+        let (dense, offset) = grid.to_grid(0);
+        assert_eq!(offset, IPoint::new(-2, 3));
+        assert_eq!((dense.width, dense.height), (4, 2));
+        assert_eq!(dense.vec, [1, 0, 0, 0, 0, 0, 0, 2]);
+        for (point, value) in grid.iter() {
+            let local = Point::try_from(point - offset).unwrap();
+            assert_eq!(dense[local], *value);
+        }
+    }
+
+    #[test]
+    fn print_bool_covers_the_bounds() {
+        let grid: SparseGrid<bool> = [(IPoint::new(-1, 0), true), (IPoint::new(1, 1), true)]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.print_bool(), "   0 ■ . . \n   1 . . ■ \n");
+    }
+}
@@ -0,0 +1,128 @@
+use std::ops::Index;
+
+use super::{
+    Grid,
+    iterators::{Edges, Neighbours},
+};
+use crate::points::{directions::Direction, ipoint::IPoint, point::Point, traits::ModuloPositive};
+
+/// A view of a non-empty grid whose edges wrap around, so that every step lands on the grid.
+#[derive(Clone, Copy, Debug)]
+pub struct Wrapping<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<'a, T> Wrapping<'a, T> {
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    pub fn wrap(&self, point: IPoint) -> Point {
+        let size = IPoint::new(self.grid.width as isize, self.grid.height as isize);
+        let wrapped = point.modulo_positive(size);
+        Point::new(wrapped.x as usize, wrapped.y as usize)
+    }
+
+    pub fn go(&self, point: Point, direction: Direction) -> Point {
+        self.wrap(IPoint::from(point) + direction.offset())
+    }
+
+    /// Always `Some`, as every point wraps onto the grid.
+    pub fn checked_index(&self, point: IPoint) -> Option<&'a T> {
+        Some(&self.grid[self.wrap(point)])
+    }
+
+    pub fn neighbours<I>(&self, point: Point, directions: I) -> Neighbours<I::IntoIter>
+    where
+        I: IntoIterator<Item = Direction>,
+    {
+        self.grid
+            .neighbours_with(point, directions, Edges::Wrapping)
+    }
+}
+
+impl<T> Index<IPoint> for Wrapping<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: IPoint) -> &T {
+        &self.grid[self.wrap(index)]
+    }
+}
+
+impl<T> Grid<T> {
+    /// `None` for a grid without cells, which there is nothing to wrap onto.
+    pub fn wrapping(&self) -> Option<Wrapping<'_, T>> {
+        (self.width > 0 && self.height > 0).then_some(Wrapping { grid: self })
+    }
+}
+
+#[cfg(test)]
+mod wrapping_tests {
+    use super::*;
+    use crate::points::directions::{CARDINALS, DIRECTIONS};
+    use test_case::test_case;
+
+    fn grid() -> Grid<usize> {
+        Grid::new((0..12).collect(), 4, 3)
+    }
+
+    #[test_case(Point::new(0, 0), Direction::North, Point::new(0, 2))]
+    #[test_case(Point::new(0, 0), Direction::West, Point::new(3, 0))]
+    #[test_case(Point::new(0, 0), Direction::NorthWest, Point::new(3, 2))]
+    #[test_case(Point::new(3, 2), Direction::SouthEast, Point::new(0, 0))]
+    #[test_case(Point::new(1, 1), Direction::East, Point::new(2, 1))]
+    fn go_wraps(point: Point, direction: Direction, expected: Point) {
+        assert_eq!(grid().wrapping().unwrap().go(point, direction), expected);
+    }
+
+    #[test_case(IPoint::new(-1, -1), 11)]
+    #[test_case(IPoint::new(4, 3), 0)]
+    #[test_case(IPoint::new(-9, 7), 7)]
+    #[test_case(IPoint::new(2, 1), 6)]
+    fn index_wraps(point: IPoint, expected: usize) {
+        let grid = grid();
+        let wrapping = grid.wrapping().unwrap();
+        assert_eq!(wrapping[point], expected);
+        assert_eq!(wrapping.checked_index(point), Some(&expected));
+    }
+
+    #[test_case(0, 0)]
+    #[test_case(0, 3)]
+    #[test_case(4, 0)]
+    fn empty_grid_does_not_wrap(width: usize, height: usize) {
+        let grid: Grid<usize> = Grid::new(Vec::new(), width, height);
+        assert!(grid.wrapping().is_none());
+    }
+
+    #[test]
+    fn neighbours_agree_with_go() {
+        let grid = grid();
+        let wrapping = grid.wrapping().unwrap();
+        for point in grid.points() {
+            let neighbours: Vec<_> = wrapping.neighbours(point, DIRECTIONS).collect();
+            let expected: Vec<_> = DIRECTIONS
+                .iter()
+                .map(|&direction| (direction, wrapping.go(point, direction)))
+                .collect();
+            assert_eq!(neighbours, expected);
+        }
+    }
+
+    #[test]
+    fn walking_around_returns_home() {
+        let grid = grid();
+        let wrapping = grid.wrapping().unwrap();
+        for direction in CARDINALS {
+            let steps = if direction.is_vertical() {
+                grid.height
+            } else {
+                grid.width
+            };
+            let mut point = Point::new(2, 1);
+            for _ in 0..steps {
+                point = wrapping.go(point, direction);
+            }
+            assert_eq!(point, Point::new(2, 1));
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    env,
+    fmt::{Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Clone, Copy, Debug)]
+pub struct Input {
+    pub day: u32,
+    pub path: &'static str,
+}
+
+#[macro_export]
+macro_rules! input {
+    ($day:expr) => {
+        $crate::input::Input::new($day, concat!(env!("CARGO_MANIFEST_DIR"), "/src/_input.txt"))
+    };
+}
+
+impl Input {
+    pub const fn new(day: u32, path: &'static str) -> Self {
+        Self { day, path }
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("{:02}.txt", self.day)
+    }
+
+    pub fn path_in(&self, dir: Option<&Path>) -> PathBuf {
+        match dir {
+            Some(dir) => dir.join(self.file_name()),
+            None => PathBuf::from(self.path),
+        }
+    }
+
+    pub fn load(&self) -> Result<String, InputError> {
+        self.load_from(input_dir().as_deref())
+    }
+
+    pub fn load_from(&self, dir: Option<&Path>) -> Result<String, InputError> {
+        read(&self.path_in(dir))
+    }
+}
+
+pub fn input_dir() -> Option<PathBuf> {
+    env::var_os(INPUT_DIR_VAR).map(PathBuf::from)
+}
+
+pub fn read(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|error| match error.kind() {
+        io::ErrorKind::NotFound => InputError::Missing(path.to_owned()),
+        _ => InputError::Unreadable(path.to_owned(), error),
+    })
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing(PathBuf),
+    Unreadable(PathBuf, io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing(path) => write!(f, "input missing at {}", path.display()),
+            Self::Unreadable(path, error) => {
+                write!(f, "unable to read {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
@@ -1,9 +1,13 @@
 use std::{
+    any::Any,
+    cmp::Ordering,
+    convert::Infallible,
     fmt::{Display, Formatter},
+    str::FromStr,
     time::{Duration, Instant},
 };
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, Debug)]
 pub enum Solution {
     I8(i8),
     I16(i16),
@@ -19,6 +23,9 @@ pub enum Solution {
     Usize(usize),
     Str(String),
     Point(Point),
+    IPoint(IPoint),
+    List(Vec<Solution>),
+    Art(String),
     None,
 }
 
@@ -39,7 +46,161 @@ impl Display for Solution {
             Self::Usize(x) => x.fmt(f),
             Self::Str(x) => x.fmt(f),
             Self::Point(x) => x.fmt(f),
-            Self::None => panic!("Cannot format NOTHING!"),
+            Self::IPoint(x) => x.fmt(f),
+            Self::List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    item.fmt(f)?;
+                }
+                Ok(())
+            }
+            Self::Art(x) => x.fmt(f),
+            Self::None => write!(f, "no answer"),
+        }
+    }
+}
+
+/// Integers are equal when their values are, whatever their width. Every other variant only
+/// equals itself, so text has to go through [`Solution::parse_like`] first.
+impl PartialEq for Solution {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Solution {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if let Some((a, b)) = self.integer().zip(other.integer()) {
+            return Some(match (a, b) {
+                ((true, a), (true, b)) => b.cmp(&a),
+                ((true, _), (false, _)) => Ordering::Less,
+                ((false, _), (true, _)) => Ordering::Greater,
+                ((false, a), (false, b)) => a.cmp(&b),
+            });
+        }
+
+        match (self, other) {
+            (Self::Str(a), Self::Str(b)) | (Self::Art(a), Self::Art(b)) => a.partial_cmp(b),
+            (Self::Point(a), Self::Point(b)) => a.partial_cmp(b),
+            (Self::IPoint(a), Self::IPoint(b)) => a.partial_cmp(b),
+            (Self::List(a), Self::List(b)) => a.partial_cmp(b),
+            (Self::None, Self::None) => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Solution {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Ok(value) = value.parse::<u128>() {
+            return Ok(Self::U128(value));
+        }
+        if let Ok(value) = value.parse::<i128>() {
+            return Ok(Self::I128(value));
+        }
+        if value.contains('\n') {
+            return Ok(Self::Art(value.to_owned()));
+        }
+        Ok(Self::Str(value.to_owned()))
+    }
+}
+
+impl Solution {
+    fn integer(&self) -> Option<(bool, u128)> {
+        let signed = |x: i128| Some((x < 0, x.unsigned_abs()));
+        match self {
+            Self::I8(x) => signed(*x as i128),
+            Self::I16(x) => signed(*x as i128),
+            Self::I32(x) => signed(*x as i128),
+            Self::I64(x) => signed(*x as i128),
+            Self::I128(x) => signed(*x),
+            Self::Isize(x) => signed(*x as i128),
+            Self::U8(x) => Some((false, *x as u128)),
+            Self::U16(x) => Some((false, *x as u128)),
+            Self::U32(x) => Some((false, *x as u128)),
+            Self::U64(x) => Some((false, *x as u128)),
+            Self::U128(x) => Some((false, *x)),
+            Self::Usize(x) => Some((false, *x as u128)),
+            _ => None,
+        }
+    }
+
+    /// Reads `text` as the same variant as `self`, if `self` would display it that way.
+    pub fn parse_like(&self, text: &str) -> Option<Solution> {
+        let parsed = match self {
+            Self::Str(_) => Self::Str(text.to_owned()),
+            Self::Art(_) => Self::Art(text.to_owned()),
+            Self::Point(_) => {
+                let (x, y) = text.strip_prefix('(')?.strip_suffix(')')?.split_once(',')?;
+                Self::Point(Point::new(x.parse().ok()?, y.parse().ok()?))
+            }
+            Self::IPoint(_) => {
+                let (x, y) = text.strip_prefix('(')?.strip_suffix(')')?.split_once(',')?;
+                Self::IPoint(IPoint::new(x.parse().ok()?, y.parse().ok()?))
+            }
+            Self::List(items) => {
+                let mut pieces = text.split(',');
+                let parsed = items
+                    .iter()
+                    .map(|item| {
+                        let commas = item.to_string().matches(',').count();
+                        let piece: Vec<&str> = pieces.by_ref().take(commas + 1).collect();
+                        item.parse_like(&piece.join(","))
+                    })
+                    .collect::<Option<_>>()?;
+                Self::List(parsed)
+            }
+            Self::None => return None,
+            _ => text
+                .parse::<Solution>()
+                .ok()
+                .filter(|parsed| parsed.integer().is_some())?,
+        };
+
+        (parsed.to_string() == text).then_some(parsed)
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::I8(_) => "i8",
+            Self::I16(_) => "i16",
+            Self::I32(_) => "i32",
+            Self::I64(_) => "i64",
+            Self::I128(_) => "i128",
+            Self::Isize(_) => "isize",
+            Self::U8(_) => "u8",
+            Self::U16(_) => "u16",
+            Self::U32(_) => "u32",
+            Self::U64(_) => "u64",
+            Self::U128(_) => "u128",
+            Self::Usize(_) => "usize",
+            Self::Str(_) => "str",
+            Self::Point(_) => "point",
+            Self::IPoint(_) => "ipoint",
+            Self::List(_) => "list",
+            Self::Art(_) => "art",
+            Self::None => "none",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Correct,
+    Wrong(String),
+    Unknown,
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::Wrong(_) => "wrong",
+            Self::Unknown => "unknown",
         }
     }
 }
@@ -78,6 +239,7 @@ impl_from!(u128, U128);
 impl_from!(usize, Usize);
 impl_from!(String, Str);
 impl_from!(Point, Point);
+impl_from!(IPoint, IPoint);
 
 impl_from_ref!(i8, I8);
 impl_from_ref!(i16, I16);
@@ -92,6 +254,7 @@ impl_from_ref!(u64, U64);
 impl_from_ref!(u128, U128);
 impl_from_ref!(usize, Usize);
 impl_from_ref!(Point, Point);
+impl_from_ref!(IPoint, IPoint);
 
 impl From<&str> for Solution {
     fn from(sol: &str) -> Self {
@@ -99,6 +262,36 @@ impl From<&str> for Solution {
     }
 }
 
+impl<T> From<Vec<T>> for Solution
+where
+    T: Into<Solution>,
+{
+    fn from(value: Vec<T>) -> Self {
+        Self::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<A, B> From<(A, B)> for Solution
+where
+    A: Into<Solution>,
+    B: Into<Solution>,
+{
+    fn from((a, b): (A, B)) -> Self {
+        Self::List(vec![a.into(), b.into()])
+    }
+}
+
+impl<A, B, C> From<(A, B, C)> for Solution
+where
+    A: Into<Solution>,
+    B: Into<Solution>,
+    C: Into<Solution>,
+{
+    fn from((a, b, c): (A, B, C)) -> Self {
+        Self::List(vec![a.into(), b.into(), c.into()])
+    }
+}
+
 impl<T> From<Option<T>> for Solution
 where
     T: Into<Solution>,
@@ -111,73 +304,344 @@ where
     }
 }
 
-use colored::Colorize;
-use points::point::Point;
+use colored::{ColoredString, Colorize};
+use error::SolveError;
+use input::InputError;
+use points::{ipoint::IPoint, point::Point};
+use timing::{Split, Timing};
+
+use crate::day::Parsed;
 
-pub fn execute(f: &dyn Fn(&str) -> Solution, input: &str, day: &str, name: &str) -> Duration {
+pub fn measure<T>(
+    f: &dyn Fn(&str) -> Result<T, SolveError>,
+    input: &str,
+    repeat: u32,
+) -> (Result<T, SolveError>, Duration) {
     let start = Instant::now();
-    let result = f(input);
-    let time = start.elapsed();
+    let mut result = f(input);
+    for _ in 1..repeat {
+        result = f(input);
+    }
+    (result, start.elapsed() / repeat)
+}
+
+pub fn sample<T>(
+    f: &dyn Fn(&str) -> Result<T, SolveError>,
+    input: &str,
+    repeat: u32,
+    warmup: u32,
+    runs: u32,
+) -> (Result<T, SolveError>, Timing) {
+    for _ in 0..warmup {
+        if let Err(error) = f(input) {
+            return (Err(error), Timing::of(&[]));
+        }
+    }
 
-    if let Solution::None = result {
-        return Duration::ZERO;
+    let (mut result, time) = measure(f, input, repeat);
+    let mut samples = vec![time];
+    while result.is_ok() && samples.len() < runs as usize {
+        let (next, time) = measure(f, input, repeat);
+        samples.push(time);
+        result = next;
     }
 
-    let ratio = time.as_micros() as f64 / (Duration::from_secs(1) / 50).as_micros() as f64;
+    (result, Timing::of(&samples))
+}
+
+pub fn sample_phases(
+    parse: &dyn Fn(&str) -> Result<Parsed, SolveError>,
+    solve: &dyn Fn(&dyn Any) -> Result<Solution, SolveError>,
+    input: &str,
+    repeat: u32,
+    warmup: u32,
+    runs: u32,
+) -> (Result<Solution, SolveError>, Split) {
+    let (parsed, parse_timing) = sample(parse, input, repeat, warmup, runs);
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            let solve_timing = Timing::of(&[]);
+            return (Err(error), Split::new(parse_timing, solve_timing));
+        }
+    };
+
+    let (result, solve_timing) = sample(&|_| solve(parsed.as_ref()), input, repeat, warmup, runs);
+    (result, Split::new(parse_timing, solve_timing))
+}
+
+pub const BUDGET: Duration = Duration::from_secs(1);
+
+fn paint(time: Duration) -> ColoredString {
+    let ratio = time.as_micros() as f64 / (BUDGET / 50).as_micros() as f64;
 
     let color = (ratio * 255.0).min(255.0) as u8;
 
+    format!("{:#?}", time).truecolor(color, 255 - color, 0)
+}
+
+pub fn report(result: &Solution, time: Duration, day: &str, name: &str, verdict: &Verdict) {
+    let answer = match verdict {
+        Verdict::Correct => format!("{} {}", render(result).bold(), "✓".green()),
+        Verdict::Wrong(expected) => format!(
+            "{} {}",
+            render(result).red().bold(),
+            format!("✗ expected {}", expected).red(),
+        ),
+        Verdict::Unknown => format!("{} {}", render(result).bold(), "?".dimmed()),
+    };
+
     println!(
         "{: >12} {:} => {}",
-        format!("{:#?}", time).truecolor(color, 255 - color, 0),
+        paint(time),
         format!("{}: {}", day, name).cyan().bold(),
-        format!("{}", result).bold(),
+        answer,
     );
+}
 
-    time
+pub fn phases(split: &Split) {
+    println!(
+        "{: >12} parse {}, solve {}",
+        "",
+        paint(split.parse.median),
+        paint(split.solve.median),
+    );
 }
 
-pub fn total(time: Duration) {
-    let ratio = time.as_micros() as f64 / (Duration::from_secs(1)).as_micros() as f64;
+pub fn statistics(timing: &Timing) {
+    println!(
+        "{: >12} min {}, median {}, mean {}, stddev {}",
+        format!("{} runs", timing.runs).dimmed(),
+        paint(timing.min),
+        paint(timing.median),
+        paint(timing.mean),
+        format!("{:#?}", timing.stddev).dimmed(),
+    );
+}
 
-    let color = (ratio * 255.0).min(255.0) as u8;
+pub fn allocations(count: usize, bytes: usize, peak: usize) {
+    println!(
+        "{: >12} {} allocated, {} peak",
+        format!("{} allocs", count).dimmed(),
+        size(bytes),
+        size(peak),
+    );
+}
 
-    let remaining = Duration::from_secs(1) - time;
+fn size(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
 
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn render(result: &Solution) -> String {
+    match result {
+        Solution::Art(art) => art
+            .lines()
+            .map(|line| format!("\n{: >13}{}", "", line))
+            .collect(),
+        _ => result.to_string(),
+    }
+}
+
+pub fn unsolved(day: &str, name: &str) {
     println!(
-        "{: >12} {}",
-        format!("{:#?}", time).truecolor(color, 255 - color, 0),
-        "Total".cyan().bold(),
+        "{: >12} {:} => {}",
+        "-".dimmed(),
+        format!("{}: {}", day, name).cyan().bold(),
+        Solution::None.to_string().dimmed(),
+    );
+}
+
+pub fn compared(baseline: Duration, ratio: f64, change: Ordering) {
+    let change = match change {
+        Ordering::Less => format!("faster {:+.1}%", ratio * 100.0).green(),
+        Ordering::Equal => format!("same {:+.1}%", ratio * 100.0).dimmed(),
+        Ordering::Greater => format!("slower {:+.1}%", ratio * 100.0).red().bold(),
+    };
+
+    println!("{: >12} {} than baseline {:#?}", "", change, baseline);
+}
+
+pub fn whole_day(day: &str, name: &str, time: Duration) {
+    println!(
+        "{: >12} {:}",
+        paint(time),
+        format!("{}: {}", day, name).cyan().bold(),
+    );
+}
+
+pub fn missing(day: &str, name: &str, error: &InputError) {
+    println!(
+        "{: >12} {:} => {}",
+        "missing".yellow(),
+        format!("{}: {}", day, name).cyan().bold(),
+        error,
+    );
+}
+
+pub fn failed(day: &str, name: &str, error: &SolveError) {
+    println!(
+        "{: >12} {:} => {}",
+        "error".red().bold(),
+        format!("{}: {}", day, name).cyan().bold(),
+        format!("{}", error).red(),
     );
+}
+
+pub fn over_budget(day: &str, name: &str, time: Duration, budget: Duration) {
+    println!(
+        "{: >12} {:} over budget by {} ({:#?} of {:#?})",
+        "budget".red().bold(),
+        format!("{}: {}", day, name).cyan().bold(),
+        format!("{:#?}", time.saturating_sub(budget)).red().bold(),
+        time,
+        budget,
+    );
+}
+
+pub fn wall(time: Duration) {
     println!(
         "{: >12} {}",
-        format!("{:#?}", remaining).truecolor(color, 255 - color, 0),
-        "Remaining".cyan().bold(),
+        format!("{:#?}", time).dimmed(),
+        "Wall clock".cyan().bold()
     );
 }
 
-pub fn day_name(day: u32) -> &'static str {
-    match day {
-        1 => "Secret Entrance",
-        2 => "Gift Shop",
-        3 => "Lobby",
-        4 => "Printing Department",
-        5 => "Cafeteria",
-        6 => "Trash Compactor",
-        7 => "Laboratories",
-        8 => "Playground",
-        9 => "Movie Theater",
-        10 => "Factory",
-        11 => "Reactor",
-        12 => "Reactor",
-        _ => "Christmas Tree Farm",
+pub fn total(time: Duration, budget: Duration) {
+    let ratio = time.as_micros() as f64 / budget.as_micros().max(1) as f64;
+
+    let color = (ratio * 255.0).min(255.0) as u8;
+
+    println!(
+        "{: >12} {}",
+        format!("{:#?}", time).truecolor(color, 255 - color, 0),
+        "Total".cyan().bold(),
+    );
+
+    match budget.checked_sub(time) {
+        Some(remaining) => println!(
+            "{: >12} {}",
+            format!("{:#?}", remaining).truecolor(color, 255 - color, 0),
+            "Remaining".cyan().bold(),
+        ),
+        None => println!(
+            "{: >12} {}",
+            format!("{:#?}", time - budget).red().bold(),
+            "Over budget".red().bold(),
+        ),
     }
 }
 
 pub mod bitmasher;
+pub mod compress;
+pub mod day;
+pub mod differential;
+pub mod error;
+pub mod generate;
 pub mod grid;
+pub mod input;
 pub mod math;
 pub mod misc;
 pub mod parse;
 pub mod points;
 pub mod solve;
+pub mod timing;
+
+pub use inventory;
+
+#[cfg(test)]
+mod solution_tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Solution::from(vec![1u8, 2, 3]), "1,2,3")]
+    #[test_case(Solution::from((4usize, "x")), "4,x")]
+    #[test_case(Solution::from(IPoint { x: -1, y: 2 }), "(-1,2)")]
+    #[test_case(Solution::Art("#.\n.#".to_owned()), "#.\n.#")]
+    #[test_case(Solution::None, "no answer")]
+    fn display(solution: Solution, expected: &str) {
+        assert_eq!(solution.to_string(), expected);
+    }
+
+    #[test_case(Solution::U64(5), Solution::Usize(5))]
+    #[test_case(Solution::I8(-1), Solution::I128(-1))]
+    #[test_case(Solution::U8(200), Solution::I32(200))]
+    #[test_case(Solution::U128(u128::MAX), Solution::U128(u128::MAX))]
+    #[test_case(Solution::from(vec![1u8, 2]), Solution::from(vec![1u64, 2]))]
+    #[test_case(Solution::from("abc"), Solution::from("abc"))]
+    fn numeric_equality(a: Solution, b: Solution) {
+        assert_eq!(a, b);
+        assert_eq!(b, a);
+    }
+
+    #[test_case(Solution::U64(5), Solution::Usize(6))]
+    #[test_case(Solution::I8(-1), Solution::U128(u128::MAX))]
+    #[test_case(Solution::U8(5), Solution::from("05"))]
+    #[test_case(Solution::U8(5), Solution::from("5"))]
+    #[test_case(Solution::I64(-7), Solution::U64(7))]
+    #[test_case(Solution::from(vec![1u8, 2]), Solution::from("1,2"))]
+    #[test_case(Solution::None, Solution::U8(0))]
+    #[test_case(Solution::Point(Point::new(1, 2)), Solution::Point(Point::new(1, 3)))]
+    fn inequality(a: Solution, b: Solution) {
+        assert_ne!(a, b);
+        assert_ne!(b, a);
+    }
+
+    #[test_case(Solution::I64(-3), Solution::U8(0))]
+    #[test_case(Solution::I64(-3), Solution::I8(-2))]
+    #[test_case(Solution::U16(9), Solution::U128(10))]
+    #[test_case(Solution::Point(Point::new(1, 2)), Solution::Point(Point::new(1, 3)))]
+    #[test_case(Solution::Point(Point::new(1, 9)), Solution::Point(Point::new(2, 0)))]
+    fn ordering(a: Solution, b: Solution) {
+        assert!(a < b);
+        assert!(b > a);
+    }
+
+    #[test_case("1023", Solution::U32(1023))]
+    #[test_case("-7", Solution::I8(-7))]
+    #[test_case("340282366920938463463374607431768211455", Solution::U128(u128::MAX))]
+    #[test_case("abc", Solution::from("abc"))]
+    #[test_case("#.\n.#", Solution::Art("#.\n.#".to_owned()))]
+    fn from_str(value: &str, expected: Solution) {
+        assert_eq!(value.parse::<Solution>(), Ok(expected));
+    }
+
+    #[test_case(Solution::I64(-7), "-7", true)]
+    #[test_case(Solution::I64(-7), "7", false)]
+    #[test_case(Solution::U64(7), "-7", false)]
+    #[test_case(Solution::U8(5), "05", false)]
+    #[test_case(Solution::from(vec![1u8, 2]), "1,2", true)]
+    #[test_case(Solution::from(vec![-1i32, 2]), "-1,2", true)]
+    #[test_case(Solution::from(vec![1u8, 2]), "1,2,3", false)]
+    #[test_case(Solution::from(vec![1u8, 2]), "1", false)]
+    #[test_case(Solution::from(vec![1u8, 2]), "1, 2", false)]
+    #[test_case(Solution::from((Point::new(1, 2), 3u8)), "(1,2),3", true)]
+    #[test_case(Solution::from((Point::new(1, 2), 3u8)), "(1,2,3)", false)]
+    #[test_case(Solution::from("1,2"), "1,2", true)]
+    #[test_case(Solution::None, "no answer", false)]
+    fn parse_like(solution: Solution, text: &str, matches: bool) {
+        assert_eq!(
+            solution.parse_like(text).as_ref() == Some(&solution),
+            matches
+        );
+    }
+
+    #[test]
+    fn render_art_below_header() {
+        let art = Solution::Art("#.\n.#".to_owned());
+        assert_eq!(render(&art), format!("\n{0}#.\n{0}.#", " ".repeat(13)));
+        assert_eq!(render(&Solution::U8(7)), "7");
+    }
+}
@@ -0,0 +1,321 @@
+use std::{
+    any::type_name,
+    fmt::{Display, Formatter},
+};
+
+use crate::error::{Position, SolveError};
+
+use super::ToDigit;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub position: Position,
+    pub expected: String,
+    pub found: Option<u8>,
+}
+
+impl ParseError {
+    pub fn reason(&self) -> String {
+        match self.found {
+            Some(byte) => format!(
+                "expected {} but found '{}'",
+                self.expected,
+                byte.escape_ascii()
+            ),
+            None => format!("expected {} but found end of input", self.expected),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.position, self.reason())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for SolveError {
+    fn from(error: ParseError) -> Self {
+        Self {
+            day: None,
+            position: Some(error.position),
+            reason: error.reason(),
+        }
+    }
+}
+
+pub trait CursorNumber: Sized + Copy {
+    const SIGNED: bool;
+    const ZERO: Self;
+
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! cursor_number {
+    ($type:ident) => {
+        impl CursorNumber for $type {
+            const SIGNED: bool = false;
+            const ZERO: Self = 0;
+
+            fn push_digit(self, digit: u8, _negative: bool) -> Option<Self> {
+                self.checked_mul(10)?.checked_add(digit as $type)
+            }
+        }
+    };
+}
+
+macro_rules! cursor_negative_number {
+    ($type:ident) => {
+        impl CursorNumber for $type {
+            const SIGNED: bool = true;
+            const ZERO: Self = 0;
+
+            fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                if negative {
+                    self.checked_mul(10)?.checked_sub(digit as $type)
+                } else {
+                    self.checked_mul(10)?.checked_add(digit as $type)
+                }
+            }
+        }
+    };
+}
+
+cursor_number!(u8);
+cursor_number!(u16);
+cursor_number!(u32);
+cursor_number!(u64);
+cursor_number!(u128);
+cursor_number!(usize);
+cursor_negative_number!(i8);
+cursor_negative_number!(i16);
+cursor_negative_number!(i32);
+cursor_negative_number!(i64);
+cursor_negative_number!(i128);
+cursor_negative_number!(isize);
+
+#[derive(Clone, Copy, Debug)]
+pub struct Cursor<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            bytes: input.as_bytes(),
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        Position::new(self.line, self.column)
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offset >= self.bytes.len()
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.offset).copied()
+    }
+
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            position: self.position(),
+            expected: expected.into(),
+            found: self.peek(),
+        }
+    }
+
+    pub fn expect_number<T: CursorNumber>(&mut self) -> Result<T, ParseError> {
+        let start = *self;
+        let negative = T::SIGNED && self.peek() == Some(b'-');
+        if negative {
+            self.next();
+        }
+
+        let Some(digit) = self.peek().and_then(|byte| byte.to_digit()) else {
+            let error = self.error("number");
+            *self = start;
+            return Err(error);
+        };
+        self.next();
+
+        let mut value = T::ZERO.push_digit(digit, negative).unwrap();
+        while let Some(digit) = self.peek().and_then(|byte| byte.to_digit()) {
+            value = match value.push_digit(digit, negative) {
+                Some(value) => value,
+                None => {
+                    let error = start.error(format!("number that fits in {}", type_name::<T>()));
+                    *self = start;
+                    return Err(error);
+                }
+            };
+            self.next();
+        }
+
+        Ok(value)
+    }
+
+    pub fn expect_byte(&mut self, expected: u8) -> Result<(), ParseError> {
+        if self.peek() == Some(expected) {
+            self.next();
+            return Ok(());
+        }
+
+        Err(self.error(format!("'{}'", expected.escape_ascii())))
+    }
+
+    pub fn expect_literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        let mut ahead = *self;
+        for expected in literal.bytes() {
+            if ahead.peek() != Some(expected) {
+                return Err(ParseError {
+                    expected: format!("\"{}\"", literal.escape_debug()),
+                    ..ahead.error("")
+                });
+            }
+            ahead.next();
+        }
+
+        *self = ahead;
+        Ok(())
+    }
+
+    pub fn expect_end(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error("end of input")),
+        }
+    }
+
+    pub fn skip_spaces(&mut self) {
+        while self.peek() == Some(b' ') {
+            self.next();
+        }
+    }
+
+    pub fn skip_line(&mut self) -> &'a str {
+        let start = self.offset;
+        while let Some(byte) = self.next() {
+            if byte == b'\n' {
+                return std::str::from_utf8(&self.bytes[start..self.offset - 1]).unwrap_or("");
+            }
+        }
+
+        std::str::from_utf8(&self.bytes[start..]).unwrap_or("")
+    }
+}
+
+impl Iterator for Cursor<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.offset += 1;
+        if byte == b'\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(byte)
+    }
+}
+
+#[cfg(test)]
+mod cursor_tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("123", 123)]
+    #[test_case("0,1", 0)]
+    #[test_case("42->7", 42)]
+    fn expect_number(input: &str, expected: u32) {
+        assert_eq!(Cursor::new(input).expect_number(), Ok(expected));
+    }
+
+    #[test_case("-12", -12)]
+    #[test_case("-128", i8::MIN)]
+    #[test_case("127", i8::MAX)]
+    fn expect_negative_number(input: &str, expected: i8) {
+        assert_eq!(Cursor::new(input).expect_number(), Ok(expected));
+    }
+
+    #[test_case("x1", 1, 1, Some(b'x'))]
+    #[test_case("-1", 1, 1, Some(b'-'))]
+    #[test_case("", 1, 1, None)]
+    #[test_case("256", 1, 1, Some(b'2'))]
+    fn expect_number_error(input: &str, line: usize, column: usize, found: Option<u8>) {
+        let error = Cursor::new(input).expect_number::<u8>().unwrap_err();
+        assert_eq!(error.position, Position::new(line, column));
+        assert_eq!(error.found, found);
+    }
+
+    #[test]
+    fn expect_sequence() {
+        let mut cursor = Cursor::new("1,2 -> 3\n4");
+        assert_eq!(cursor.expect_number(), Ok(1u8));
+        assert_eq!(cursor.expect_byte(b','), Ok(()));
+        assert_eq!(cursor.expect_number(), Ok(2u8));
+        cursor.skip_spaces();
+        assert_eq!(cursor.expect_literal("->"), Ok(()));
+        cursor.skip_spaces();
+        assert_eq!(cursor.expect_number(), Ok(3u8));
+        assert_eq!(cursor.expect_byte(b'\n'), Ok(()));
+        assert_eq!(cursor.position(), Position::new(2, 1));
+        assert_eq!(cursor.expect_number(), Ok(4u8));
+        assert_eq!(cursor.expect_end(), Ok(()));
+    }
+
+    #[test]
+    fn mismatch_reports_offending_byte() {
+        let mut cursor = Cursor::new("1\n2;3");
+        cursor.skip_line();
+        cursor.expect_number::<u8>().unwrap();
+        let error = cursor.expect_byte(b',').unwrap_err();
+        assert_eq!(error.position, Position::new(2, 2));
+        assert_eq!(error.found, Some(b';'));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected ',' but found ';'"
+        );
+    }
+
+    #[test]
+    fn literal_mismatch_points_at_first_difference() {
+        let mut cursor = Cursor::new("-<");
+        let error = cursor.expect_literal("->").unwrap_err();
+        assert_eq!(error.position, Position::new(1, 2));
+        assert_eq!(error.found, Some(b'<'));
+        assert_eq!(cursor.offset(), 0);
+    }
+
+    #[test]
+    fn skip_line() {
+        let mut cursor = Cursor::new("ab\ncd");
+        assert_eq!(cursor.skip_line(), "ab");
+        assert_eq!(cursor.position(), Position::new(2, 1));
+        assert_eq!(cursor.skip_line(), "cd");
+        assert!(cursor.is_empty());
+        assert_eq!(cursor.skip_line(), "");
+    }
+
+    #[test]
+    fn into_solve_error() {
+        let error: SolveError = Cursor::new("\n").expect_byte(b'x').unwrap_err().into();
+        assert_eq!(error.position, Some(Position::new(1, 1)));
+        assert_eq!(error.reason, "expected 'x' but found '\\n'");
+    }
+}
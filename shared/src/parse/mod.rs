@@ -1,3 +1,9 @@
+pub mod cursor;
+
+use std::fmt::{Display, Formatter};
+
+use crate::error::SolveError;
+
 pub trait ToDigit {
     fn to_digit(&self) -> Option<u8>;
 }
@@ -46,6 +52,33 @@ pub trait ParsableStrict<T>: Iterator {
     fn next_number_strict(&mut self) -> (Option<T>, Option<u8>);
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Overflow {
+    pub type_name: &'static str,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "number does not fit in {}", self.type_name)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+impl From<Overflow> for SolveError {
+    fn from(error: Overflow) -> Self {
+        SolveError::new(error.to_string())
+    }
+}
+
+pub trait ParsableChecked<T>: Iterator {
+    fn next_number_checked(&mut self) -> Result<Option<T>, Overflow>;
+}
+
+pub trait ParsableStrictChecked<T>: Iterator {
+    fn next_number_strict_checked(&mut self) -> Result<(Option<T>, Option<u8>), Overflow>;
+}
+
 macro_rules! parsable_number {
     ($type:ident) => {
         impl<T: Iterator<Item = u8>> Parsable<$type> for T {
@@ -240,6 +273,131 @@ macro_rules! parsable_float_number_strict {
     };
 }
 
+macro_rules! parsable_number_checked {
+    ($type:ident) => {
+        impl<T: Iterator<Item = u8>> ParsableChecked<$type> for T {
+            fn next_number_checked(&mut self) -> Result<Option<$type>, Overflow> {
+                let overflow = Overflow {
+                    type_name: stringify!($type),
+                };
+                let mut value: Option<$type> = None;
+                for byte in self {
+                    if let Some(digit) = byte.to_digit() {
+                        let current = value.unwrap_or(0);
+                        value = Some(
+                            current
+                                .checked_mul(10)
+                                .and_then(|current| current.checked_add(digit as $type))
+                                .ok_or(overflow)?,
+                        );
+                    } else if value.is_some() {
+                        return Ok(value);
+                    }
+                }
+
+                Ok(value)
+            }
+        }
+
+        impl<T: Iterator<Item = u8>> ParsableStrictChecked<$type> for T {
+            fn next_number_strict_checked(
+                &mut self,
+            ) -> Result<(Option<$type>, Option<u8>), Overflow> {
+                let overflow = Overflow {
+                    type_name: stringify!($type),
+                };
+                let mut value: Option<$type> = None;
+                for byte in self {
+                    if let Some(digit) = byte.to_digit() {
+                        let current = value.unwrap_or(0);
+                        value = Some(
+                            current
+                                .checked_mul(10)
+                                .and_then(|current| current.checked_add(digit as $type))
+                                .ok_or(overflow)?,
+                        );
+                    } else {
+                        return Ok((value, Some(byte)));
+                    }
+                }
+
+                Ok((value, None))
+            }
+        }
+    };
+}
+
+macro_rules! parsable_negative_number_checked {
+    ($type:ident) => {
+        impl<T: Iterator<Item = u8>> ParsableChecked<$type> for T {
+            fn next_number_checked(&mut self) -> Result<Option<$type>, Overflow> {
+                let overflow = Overflow {
+                    type_name: stringify!($type),
+                };
+                let mut negative = false;
+                let mut value: Option<$type> = None;
+                for byte in self {
+                    if let Some(digit) = byte.to_digit() {
+                        let current = value.unwrap_or(0).checked_mul(10).ok_or(overflow)?;
+                        value = Some(
+                            if negative {
+                                current.checked_sub(digit as $type)
+                            } else {
+                                current.checked_add(digit as $type)
+                            }
+                            .ok_or(overflow)?,
+                        );
+                    } else if value.is_some() {
+                        return Ok(value);
+                    } else if byte == b'-' {
+                        negative = true;
+                    } else {
+                        negative = false;
+                    }
+                }
+
+                Ok(value)
+            }
+        }
+
+        impl<T: Iterator<Item = u8>> ParsableStrictChecked<$type> for T {
+            fn next_number_strict_checked(
+                &mut self,
+            ) -> Result<(Option<$type>, Option<u8>), Overflow> {
+                let overflow = Overflow {
+                    type_name: stringify!($type),
+                };
+                let mut negative = false;
+                let mut value: Option<$type> = None;
+                for byte in self {
+                    if let Some(digit) = byte.to_digit() {
+                        let current = value.unwrap_or(0).checked_mul(10).ok_or(overflow)?;
+                        value = Some(
+                            if negative {
+                                current.checked_sub(digit as $type)
+                            } else {
+                                current.checked_add(digit as $type)
+                            }
+                            .ok_or(overflow)?,
+                        );
+                    } else if byte == b'-' {
+                        if !negative {
+                            value = value
+                                .map(|value| value.checked_neg().ok_or(overflow))
+                                .transpose()?;
+                        }
+                        negative = true;
+                    } else {
+                        return Ok((value, Some(byte)));
+                    }
+                }
+
+                Ok((value, None))
+            }
+        }
+    };
+}
+
 parsable_number!(u8);
 parsable_number!(u16);
 parsable_number!(u32);
@@ -268,3 +426,65 @@ parsable_negative_number_strict!(i32);
 parsable_negative_number_strict!(i64);
 parsable_negative_number_strict!(i128);
 parsable_negative_number_strict!(isize);
+parsable_number_checked!(u8);
+parsable_number_checked!(u16);
+parsable_number_checked!(u32);
+parsable_number_checked!(u64);
+parsable_number_checked!(u128);
+parsable_number_checked!(usize);
+parsable_negative_number_checked!(i8);
+parsable_negative_number_checked!(i16);
+parsable_negative_number_checked!(i32);
+parsable_negative_number_checked!(i64);
+parsable_negative_number_checked!(i128);
+parsable_negative_number_checked!(isize);
+
+#[cfg(test)]
+mod parse_tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("12 34", Some(12))]
+    #[test_case("abc255", Some(255))]
+    #[test_case("", None)]
+    fn next_number_checked(input: &str, expected: Option<u8>) {
+        assert_eq!(input.bytes().next_number_checked(), Ok(expected));
+    }
+
+    #[test_case("-128", Some(i8::MIN))]
+    #[test_case("x-5,", Some(-5))]
+    #[test_case("127", Some(i8::MAX))]
+    fn next_negative_number_checked(input: &str, expected: Option<i8>) {
+        assert_eq!(input.bytes().next_number_checked(), Ok(expected));
+    }
+
+    #[test]
+    fn overflow() {
+        let error = "256".bytes().next_number_checked() as Result<Option<u8>, _>;
+        assert_eq!(error, Err(Overflow { type_name: "u8" }));
+        let error = "-129".bytes().next_number_checked() as Result<Option<i8>, _>;
+        assert_eq!(error, Err(Overflow { type_name: "i8" }));
+        let error: Result<Option<u128>, _> = "340282366920938463463374607431768211456"
+            .bytes()
+            .next_number_checked();
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "number does not fit in u128"
+        );
+    }
+
+    #[test_case("42,", (Some(42), Some(b',')))]
+    #[test_case("42", (Some(42), None))]
+    #[test_case(",", (None, Some(b',')))]
+    fn next_number_strict_checked(input: &str, expected: (Option<u64>, Option<u8>)) {
+        assert_eq!(input.bytes().next_number_strict_checked(), Ok(expected));
+    }
+
+    #[test]
+    fn strict_overflow() {
+        let result = "1000,".bytes().next_number_strict_checked() as Result<(Option<u8>, _), _>;
+        assert_eq!(result, Err(Overflow { type_name: "u8" }));
+        let result = "-12,".bytes().next_number_strict_checked();
+        assert_eq!(result, Ok((Some(-12i16), Some(b','))));
+    }
+}
@@ -1,5 +1,7 @@
 use core::fmt;
 
+use super::ipoint::IPoint;
+
 pub const CARDINALS: [Direction; 4] = [
     Direction::North,
     Direction::East,
@@ -18,6 +20,13 @@ pub const DIRECTIONS: [Direction; 8] = [
     Direction::NorthWest,
 ];
 
+pub const ORDINALS: [Direction; 4] = [
+    Direction::NorthEast,
+    Direction::SouthEast,
+    Direction::SouthWest,
+    Direction::NorthWest,
+];
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Direction {
     North = 0,
@@ -137,6 +146,20 @@ impl Direction {
         }
     }
 
+    /// One step in this direction, with north being negative y.
+    pub fn offset(&self) -> IPoint {
+        match self {
+            Direction::North => IPoint::new(0, -1),
+            Direction::NorthEast => IPoint::new(1, -1),
+            Direction::East => IPoint::new(1, 0),
+            Direction::SouthEast => IPoint::new(1, 1),
+            Direction::South => IPoint::new(0, 1),
+            Direction::SouthWest => IPoint::new(-1, 1),
+            Direction::West => IPoint::new(-1, 0),
+            Direction::NorthWest => IPoint::new(-1, -1),
+        }
+    }
+
     pub fn is_vertical(&self) -> bool {
         return *self == Direction::North || *self == Direction::South;
     }
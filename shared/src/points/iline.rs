@@ -205,9 +205,30 @@ impl ILine {
 mod iline_tests {
     use super::ILine;
     use crate::*;
+    use generate::Rng;
     use points::{iline::Intersection, ipoint::IPoint};
     use test_case::test_case;
 
+    fn ipoint(rng: &mut Rng) -> IPoint {
+        IPoint::new(
+            rng.range(0, 40) as isize - 20,
+            rng.range(0, 40) as isize - 20,
+        )
+    }
+
+    /// Mostly straight lines, as the puzzles use them, with some diagonal and skewed ones.
+    fn iline(rng: &mut Rng) -> ILine {
+        let from = ipoint(rng);
+        let mut to = ipoint(rng);
+        match rng.below(4) {
+            0 => to.x = from.x,
+            1 => to.y = from.y,
+            2 => to.y = from.y + (to.x - from.x),
+            _ => {}
+        }
+        ILine::new(from, to)
+    }
+
     #[test_case(
         ILine::new(IPoint::new(1, 0), IPoint::new(1, 2)),
         ILine::new(IPoint::new(0, 1), IPoint::new(2, 1)),
@@ -247,4 +268,58 @@ mod iline_tests {
     fn intersection_endpoint_test(a: ILine, b: ILine, point: IPoint) {
         assert_eq!(a.find_intersect(&b), Intersection::Point(point));
     }
+
+    #[test]
+    fn points_run_from_min_to_max_without_gaps() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            let line = iline(&mut rng);
+            let points = line.points();
+            let span = line.max - line.min;
+
+            assert_eq!(points.first(), Some(&line.min), "{}", line);
+            assert_eq!(points.last(), Some(&line.max), "{}", line);
+            assert_eq!(
+                points.len(),
+                span.x.unsigned_abs().max(span.y.unsigned_abs()) + 1
+            );
+            for pair in points.windows(2) {
+                let step = pair[1] - pair[0];
+                assert!(step.x.abs() <= 1 && step.y.abs() <= 1 && step != IPoint::new(0, 0));
+            }
+        }
+    }
+
+    #[test]
+    fn straight_points_lie_on_the_line() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            let line = iline(&mut rng);
+            if line.min.x == line.max.x || line.min.y == line.max.y {
+                assert!(
+                    line.points().iter().all(|point| line.contains(*point)),
+                    "{}",
+                    line
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn find_intersect_is_symmetric() {
+        let mut rng = Rng::new(2);
+        for _ in 0..5000 {
+            let (a, b) = (iline(&mut rng), iline(&mut rng));
+            let intersection = a.find_intersect(&b);
+            assert_eq!(intersection, b.find_intersect(&a), "{} and {}", a, b);
+
+            let straight = |line: &ILine| line.min.x == line.max.x || line.min.y == line.max.y;
+            if straight(&a)
+                && straight(&b)
+                && let Intersection::Point(point) = intersection
+            {
+                assert!(a.contains(point) && b.contains(point), "{} and {}", a, b);
+            }
+        }
+    }
 }
@@ -378,3 +378,94 @@ impl Sum for IPoint {
         iter.fold(IPoint::new(0, 0), |acc, item| acc + item)
     }
 }
+
+#[cfg(test)]
+mod ipoint_tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    fn ipoint(rng: &mut Rng) -> IPoint {
+        IPoint::new(
+            rng.range(0, 2000) as isize - 1000,
+            rng.range(0, 2000) as isize - 1000,
+        )
+    }
+
+    #[test]
+    fn add_and_sub_form_a_group() {
+        let mut rng = Rng::new(0);
+        let zero = IPoint::new(0, 0);
+        for _ in 0..1000 {
+            let (a, b, c) = (ipoint(&mut rng), ipoint(&mut rng), ipoint(&mut rng));
+            assert_eq!(a + b, b + a);
+            assert_eq!((a + b) + c, a + (b + c));
+            assert_eq!(a + zero, a);
+            assert_eq!(a - a, zero);
+            assert_eq!(a + b - b, a);
+            assert_eq!([a, b, c].into_iter().sum::<IPoint>(), a + b + c);
+        }
+    }
+
+    #[test]
+    fn scaling_distributes() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            let (a, b) = (ipoint(&mut rng), ipoint(&mut rng));
+            let k = rng.range(1, 20) as isize;
+            assert_eq!((a + b) * k, a * k + b * k);
+            assert_eq!(a * k / k, a);
+            assert_eq!(a * IPoint::new(k, k), a * k);
+        }
+    }
+
+    #[test]
+    fn modulo_positive_is_in_range_and_congruent() {
+        let mut rng = Rng::new(2);
+        for _ in 0..1000 {
+            let a = ipoint(&mut rng);
+            let m = IPoint::new(rng.range(1, 50) as isize, rng.range(1, 50) as isize);
+            let r = a.modulo_positive(m);
+            assert!(
+                (0..m.x).contains(&r.x) && (0..m.y).contains(&r.y),
+                "{} mod {}",
+                a,
+                m
+            );
+            assert_eq!((a - r).modulo(m), IPoint::new(0, 0));
+
+            let mut assigned = a;
+            assigned.module_positive_assign(m);
+            assert_eq!(assigned, r);
+            assert_eq!(
+                a.modulo_positive(m.x),
+                IPoint::new(r.x, a.y.rem_euclid(m.x))
+            );
+        }
+    }
+
+    #[test]
+    fn dot_is_symmetric_and_cross_antisymmetric() {
+        let mut rng = Rng::new(3);
+        for _ in 0..1000 {
+            let (a, b) = (ipoint(&mut rng), ipoint(&mut rng));
+            assert_eq!(a.dot(b), b.dot(a));
+            assert_eq!(a.cross(b), -b.cross(a));
+            assert_eq!(a.cross(a), 0);
+            assert_eq!(a.dot(a), a.x * a.x + a.y * a.y);
+        }
+    }
+
+    #[test]
+    fn distance_is_a_metric() {
+        let mut rng = Rng::new(4);
+        for _ in 0..1000 {
+            let (a, b, c) = (ipoint(&mut rng), ipoint(&mut rng), ipoint(&mut rng));
+            assert_eq!(a.distance_to(b), b.distance_to(a));
+            assert_eq!(
+                a.distance_to(b),
+                (a - b).absolute().x as usize + (a - b).absolute().y as usize
+            );
+            assert!(a.distance_to(c) <= a.distance_to(b) + b.distance_to(c));
+        }
+    }
+}
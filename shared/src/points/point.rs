@@ -42,9 +42,7 @@ impl Ord for Point {
 
 impl PartialOrd for Point {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.x
-            .partial_cmp(&other.x)
-            .or_else(|| self.y.partial_cmp(&other.y))
+        Some(self.cmp(other))
     }
 }
 
@@ -319,3 +317,65 @@ impl Sum for Point {
         iter.fold(Point::new(0, 0), |acc, item| acc + item)
     }
 }
+
+#[cfg(test)]
+mod point_tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    fn point(rng: &mut Rng) -> Point {
+        Point::new(rng.below(1000), rng.below(1000))
+    }
+
+    #[test]
+    fn sub_undoes_add() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            let (a, b) = (point(&mut rng), point(&mut rng));
+            assert_eq!(a + b - b, a);
+            assert_eq!(a + b, b + a);
+            assert_eq!(a.checked_add(b), Some(a + b));
+        }
+    }
+
+    #[test]
+    fn checked_sub_fails_only_below_zero() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            let (a, b) = (point(&mut rng), point(&mut rng));
+            let fits = a.x >= b.x && a.y >= b.y;
+            assert_eq!(a.checked_sub(b).is_some(), fits, "{} - {}", a, b);
+        }
+    }
+
+    #[test]
+    fn distance_is_a_metric() {
+        let mut rng = Rng::new(2);
+        for _ in 0..1000 {
+            let (a, b, c) = (point(&mut rng), point(&mut rng), point(&mut rng));
+            assert_eq!(a.distance_to(a), 0);
+            assert_eq!(a.distance_to(b), b.distance_to(a));
+            assert!(a.distance_to(c) <= a.distance_to(b) + b.distance_to(c));
+        }
+    }
+
+    #[test]
+    fn ordering_is_consistent() {
+        let mut rng = Rng::new(3);
+        for _ in 0..1000 {
+            let (a, b) = (point(&mut rng), point(&mut rng));
+            assert_eq!(a.partial_cmp(&b), Some(a.cmp(&b)), "{} vs {}", a, b);
+            assert_eq!(a.cmp(&b), (a.x, a.y).cmp(&(b.x, b.y)));
+        }
+    }
+
+    #[test]
+    fn converts_through_ipoint() {
+        let mut rng = Rng::new(4);
+        for _ in 0..1000 {
+            let a = point(&mut rng);
+            assert_eq!(Point::try_from(IPoint::from(a)), Ok(a));
+        }
+        assert!(Point::try_from(IPoint::new(-1, 0)).is_err());
+    }
+}
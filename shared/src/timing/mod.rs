@@ -0,0 +1,139 @@
+use std::{ops::Add, time::Duration};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Timing {
+    pub runs: u32,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Timing {
+    pub fn single(time: Duration) -> Self {
+        Self {
+            runs: 1,
+            min: time,
+            median: time,
+            mean: time,
+            stddev: Duration::ZERO,
+        }
+    }
+
+    pub fn of(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self {
+                runs: 0,
+                ..Self::single(Duration::ZERO)
+            };
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let runs = sorted.len();
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+
+        let mean = sorted.iter().sum::<Duration>() / runs as u32;
+        let variance = if runs > 1 {
+            sorted
+                .iter()
+                .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                .sum::<f64>()
+                / (runs - 1) as f64
+        } else {
+            0.0
+        };
+
+        Self {
+            runs: runs as u32,
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl Add for Timing {
+    type Output = Timing;
+
+    fn add(self, other: Timing) -> Timing {
+        let variance = self.stddev.as_secs_f64().powi(2) + other.stddev.as_secs_f64().powi(2);
+        Self {
+            runs: self.runs.min(other.runs),
+            min: self.min + other.min,
+            median: self.median + other.median,
+            mean: self.mean + other.mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Split {
+    pub parse: Timing,
+    pub solve: Timing,
+}
+
+impl Split {
+    pub fn new(parse: Timing, solve: Timing) -> Self {
+        Self { parse, solve }
+    }
+
+    pub fn total(&self) -> Timing {
+        self.parse + self.solve
+    }
+}
+
+#[cfg(test)]
+mod timing_tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values
+            .iter()
+            .map(|&value| Duration::from_micros(value))
+            .collect()
+    }
+
+    #[test_case(&[5], 5000, 5000, 5000)]
+    #[test_case(&[9, 1, 5], 1000, 5000, 5000)]
+    #[test_case(&[4, 1, 3, 8], 1000, 3500, 4000)]
+    fn statistics(samples: &[u64], min: u64, median: u64, mean: u64) {
+        let timing = Timing::of(&micros(samples));
+        assert_eq!(timing.runs, samples.len() as u32);
+        assert_eq!(timing.min, Duration::from_nanos(min));
+        assert_eq!(timing.median, Duration::from_nanos(median));
+        assert_eq!(timing.mean, Duration::from_nanos(mean));
+    }
+
+    #[test]
+    fn standard_deviation() {
+        let timing = Timing::of(&micros(&[2, 4, 4, 4, 5, 5, 7, 9]));
+        assert_eq!(timing.stddev.as_nanos(), 2138);
+        assert_eq!(Timing::of(&micros(&[3])).stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn single() {
+        let time = Duration::from_millis(3);
+        assert_eq!(Timing::of(&[time]), Timing::single(time));
+    }
+
+    #[test]
+    fn split_total() {
+        let parse = Timing::of(&micros(&[1, 3]));
+        let solve = Timing::of(&micros(&[10, 20, 30]));
+        let total = Split::new(parse, solve).total();
+        assert_eq!(total.runs, 2);
+        assert_eq!(total.min, Duration::from_micros(11));
+        assert_eq!(total.median, Duration::from_micros(22));
+        assert_eq!(total.mean, Duration::from_micros(22));
+    }
+}